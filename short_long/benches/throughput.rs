@@ -0,0 +1,78 @@
+//! Compares the single-pass [`short_long::try_ratio_with`] with the original
+//! implementation, which collected every word into a `Vec` before counting,
+//! and with [`short_long::stats`], which also gathers the readability
+//! counts, on `formalSentences.txt`.
+//!
+//! Run with `cargo bench`.
+
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use short_long::Config;
+
+const RUNS: u32 = 50;
+
+/// The ratio as originally computed, apart from splitting words like
+/// Python so that both versions count the same words.
+fn collect_then_count(text: &str, config: &Config) -> f64 {
+    let split: Vec<&str> = config
+        .tokenizer
+        .tokens(text)
+        .filter(|&s| !config.is_stopword(s))
+        .collect();
+    let mut l = 0.0;
+    for i in &split {
+        if config.unit.len(i) > config.threshold {
+            l += 1.0;
+        }
+    }
+    l / (split.len() as f64)
+}
+
+fn single_pass(text: &str, config: &Config) -> f64 {
+    short_long::try_ratio_with(text, config).unwrap()
+}
+
+fn with_readability(text: &str, config: &Config) -> f64 {
+    short_long::stats(text, config).ratio().unwrap()
+}
+
+/// Returns the mean time of one call of `f` on `text`.
+fn time(text: &str, config: &Config, f: fn(&str, &Config) -> f64) -> Duration {
+    black_box(f(text, config));
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(f(black_box(text), config));
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../formalSentences.txt");
+    let text = fs::read_to_string(path).unwrap();
+    let config = Config::default();
+    assert_eq!(
+        collect_then_count(&text, &config),
+        single_pass(&text, &config)
+    );
+
+    let mb = text.len() as f64 / 1e6;
+    for (name, f) in [
+        (
+            "collect then count",
+            collect_then_count as fn(&str, &Config) -> f64,
+        ),
+        ("single pass", single_pass),
+        ("with readability", with_readability),
+    ] {
+        let elapsed = time(&text, &config, f);
+        println!(
+            "{:<20} {:>9.3} ms  {:>8.1} MB/s",
+            name,
+            elapsed.as_secs_f64() * 1e3,
+            mb / elapsed.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,550 @@
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#ifndef SHORT_LONG_H
+#define SHORT_LONG_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Longest possible encoding written by [`short_long_stats_encode`].
+#define SHORT_LONG_STATS_MAX_ENCODED_LEN (1 + (9 * 10))
+
+// Outcome of a checked call, returned instead of a bare NaN.
+typedef enum ShortLongStatus {
+  // The ratio was computed.
+  SHORT_LONG_STATUS_OK = 0,
+  // The text is not valid UTF-8; the byte offset of the first invalid
+  // sequence is reported separately.
+  SHORT_LONG_STATUS_INVALID_UTF8 = 1,
+  // The stopwords in the [`ShortLongConfig`] are null or not valid UTF-8,
+  // or its unit or tokenizer is unknown.
+  SHORT_LONG_STATUS_INVALID_CONFIG = 2,
+  // No words were left after removing stopwords, so the ratio is
+  // undefined rather than 0.
+  SHORT_LONG_STATUS_EMPTY_INPUT = 3,
+  // The text pointer was null but its length was not 0.
+  SHORT_LONG_STATUS_NULL_POINTER = 4,
+  // The library panicked. This is a bug in the library, not the input.
+  SHORT_LONG_STATUS_PANIC = 5,
+  // An argument other than the text is malformed, such as batch offsets
+  // that decrease or run past the end of the buffer.
+  SHORT_LONG_STATUS_INVALID_ARGUMENT = 6,
+  // An output buffer is too small; the required size is reported
+  // separately.
+  SHORT_LONG_STATUS_BUFFER_TOO_SMALL = 7,
+} ShortLongStatus;
+
+// What a word's length is measured in.
+typedef enum ShortLongLengthUnit {
+  // UTF-8 bytes, as the ratio was originally computed.
+  SHORT_LONG_LENGTH_UNIT_BYTES = 0,
+  // Unicode scalar values (code points), like Python's `len()` on `str`.
+  SHORT_LONG_LENGTH_UNIT_CHARS = 1,
+  // Extended grapheme clusters, i.e. user-perceived characters.
+  SHORT_LONG_LENGTH_UNIT_GRAPHEMES = 2,
+} ShortLongLengthUnit;
+
+// How text is split into words.
+typedef enum ShortLongTokenizer {
+  // Splits on runs of whitespace and drops empty words, exactly like
+  // Python's `str.split()` with no arguments.
+  SHORT_LONG_TOKENIZER_PYTHON = 0,
+  // Splits on every single whitespace character, so doubled spaces and
+  // line breaks produce empty words. This is how the ratio was originally
+  // computed.
+  SHORT_LONG_TOKENIZER_LEGACY = 1,
+} ShortLongTokenizer;
+
+// Opaque handle counting text fed in chunks; see [`Accumulator`].
+typedef struct ShortLongState ShortLongState;
+
+// C-compatible counterpart of [`Config`].
+typedef struct ShortLongConfig {
+  // Words longer than this many `unit`s count as long.
+  size_t threshold;
+  // What word lengths are measured in, as a [`LengthUnit`] value.
+  uint32_t unit;
+  // Whitespace-separated UTF-8 list of words to ignore. May be null when
+  // `stopwords_len` is 0.
+  const uint8_t *stopwords;
+  // Length of `stopwords` in bytes.
+  size_t stopwords_len;
+  // Whether a word must match a stopword's case exactly to be ignored.
+  bool case_sensitive;
+  // How the text is split into words, as a [`Tokenizer`] value. This and
+  // `unit` are plain integers so that an unknown value is an error rather
+  // than undefined behaviour.
+  uint32_t tokenizer;
+} ShortLongConfig;
+
+// A ratio together with the status explaining whether it is meaningful.
+typedef struct ShortLongResult {
+  enum ShortLongStatus status;
+  // The ratio if `status` is [`ShortLongStatus::Ok`], NaN otherwise.
+  double ratio;
+} ShortLongResult;
+
+// C-compatible counterpart of [`Stats`], with the ratio and
+// [`Readability`] indices filled in.
+typedef struct ShortLongStats {
+  // Every word in the text, stopwords included.
+  uint64_t total_tokens;
+  // Words left after removing stopwords: the ratio's denominator.
+  uint64_t filtered_tokens;
+  // Filtered words longer than the threshold: the ratio's numerator.
+  uint64_t long_tokens;
+  // Summed length of the filtered words, in the configured unit.
+  uint64_t total_chars;
+  // `long_tokens / filtered_tokens`, or NaN if `filtered_tokens` is 0.
+  double ratio;
+  // Words containing a letter or digit, stopwords included.
+  uint64_t words;
+  // Words that end a sentence.
+  uint64_t sentences;
+  // Estimated syllables in `words`.
+  uint64_t syllables;
+  // Words of three or more syllables.
+  uint64_t polysyllables;
+  // Letters and digits in `words`.
+  uint64_t letters;
+  // Flesch Reading Ease, or NaN if `words` is 0, like every index below.
+  double flesch_reading_ease;
+  // Flesch–Kincaid Grade Level.
+  double flesch_kincaid_grade;
+  // Gunning Fog index.
+  double gunning_fog;
+  // SMOG grade.
+  double smog;
+  // Coleman–Liau index.
+  double coleman_liau;
+  // Automated Readability Index.
+  double automated_readability;
+  // `polysyllables / words`, or NaN if `words` is 0.
+  double polysyllabic_ratio;
+  // `words / sentences`, the average sentence length, or NaN if `words`
+  // is 0. A text without a sentence terminator counts as one sentence.
+  double words_per_sentence;
+} ShortLongStats;
+
+// C-compatible counterpart of [`DiversityOptions`].
+typedef struct ShortLongDiversityOptions {
+  // Whether words that differ only in case count as different types.
+  bool case_sensitive;
+  // Length in words of the moving window for MATTR.
+  size_t window;
+  // Type-token ratio at which MTLD closes a factor. Must not be NaN.
+  double mtld_threshold;
+  // Number of words drawn in HD-D's hypothetical samples.
+  size_t sample_size;
+} ShortLongDiversityOptions;
+
+// C-compatible counterpart of [`Diversity`], with NaN for the measures
+// that are undefined.
+typedef struct ShortLongDiversity {
+  // Words left after removing stopwords and punctuation.
+  uint64_t tokens;
+  // Distinct words.
+  uint64_t types;
+  // Types that occur exactly once.
+  uint64_t hapax_legomena;
+  // Types that occur exactly twice.
+  uint64_t dis_legomena;
+  // `types / tokens`.
+  double ttr;
+  // Moving-average type-token ratio.
+  double mattr;
+  // MTLD, or NaN if no run of words falls to the threshold.
+  double mtld;
+  // HD-D, or NaN if the text is shorter than the sample size.
+  double hdd;
+} ShortLongDiversity;
+
+// Where one piece of a text lies: the bytes from `start` up to `end`.
+typedef struct ShortLongSpan {
+  size_t start;
+  size_t end;
+} ShortLongSpan;
+
+// One document of a batch: `len` bytes of UTF-8 text at `ptr`.
+typedef struct ShortLongDocument {
+  // May be null when `len` is 0.
+  const uint8_t *ptr;
+  size_t len;
+} ShortLongDocument;
+
+// One entry of [`ShortLongTopWords`]: `len` bytes of UTF-8 at `word`,
+// not NUL-terminated, which occur `count` times.
+typedef struct ShortLongWordCount {
+  const uint8_t *word;
+  size_t len;
+  uint64_t count;
+} ShortLongWordCount;
+
+// The most frequent words of a text, owned by the library until passed to
+// [`short_long_top_words_free`].
+typedef struct ShortLongTopWords {
+  // `count` entries, most frequent first, or null if `count` is 0.
+  struct ShortLongWordCount *words;
+  size_t count;
+} ShortLongTopWords;
+
+// Returns the default preset: Python-style word splitting and a threshold
+// of 8 characters, ignoring "the" and "a" case-sensitively.
+struct ShortLongConfig short_long_default_config(void);
+
+// Returns the fraction of words longer than 8 characters in the UTF-8
+// string at `ptr`, ignoring "the" and "a". Returns NaN if there are no such
+// words, the bytes are not UTF-8, or `ptr` is null with a non-zero `len`.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes.
+double short_long(const uint8_t *ptr, size_t len);
+
+// Returns the fraction of words with three or more syllables in the UTF-8
+// string at `ptr`, stopwords included. Returns NaN if there are no words,
+// the bytes are not UTF-8, or `ptr` is null with a non-zero `len`.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes.
+double short_long_polysyllabic(const uint8_t *ptr, size_t len);
+
+// Like [`short_long`], but with the threshold, unit and stopwords taken from
+// `config`, or the default preset if `config` is null. Also returns NaN if
+// the stopwords are not UTF-8.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, and `config` must be
+// null or point to a valid [`ShortLongConfig`].
+double short_long_with_config(const uint8_t *ptr, size_t len, const struct ShortLongConfig *config);
+
+// Like [`short_long_with_config`], but validates the text and reports
+// failures through the returned status instead of NaN.
+//
+// On success the ratio is written to `ratio_out`. If the text is not UTF-8,
+// [`ShortLongStatus::InvalidUtf8`] is returned and the byte offset of the
+// first invalid sequence is written to `error_offset_out`, unless `lossy` is
+// set, in which case invalid sequences are replaced with U+FFFD and the
+// ratio is computed anyway. [`ShortLongStatus::EmptyInput`] is returned if
+// no words are left after removing stopwords. Either out-parameter may be
+// null.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], and each out-parameter must
+// be null or valid for writes.
+enum ShortLongStatus short_long_checked(const uint8_t *ptr,
+                                        size_t len,
+                                        const struct ShortLongConfig *config,
+                                        bool lossy,
+                                        double *ratio_out,
+                                        size_t *error_offset_out);
+
+// Like [`short_long_with_config`], but returns the ratio together with a
+// status, so that "no words" ([`ShortLongStatus::EmptyInput`]) can be told
+// apart from "no long words" (a ratio of 0).
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, and `config` must be
+// null or point to a valid [`ShortLongConfig`].
+struct ShortLongResult short_long_result(const uint8_t *ptr,
+                                         size_t len,
+                                         const struct ShortLongConfig *config);
+
+// Counts the words of the UTF-8 string at `ptr` and writes every count,
+// plus the ratio, to `stats_out`.
+//
+// Text without words is not an error here: the counts are still written,
+// with a NaN ratio, and [`ShortLongStatus::Ok`] is returned. If the text is
+// not UTF-8, [`ShortLongStatus::InvalidUtf8`] is returned and `stats_out`
+// is left untouched.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], and `stats_out` must be
+// null or valid for writes.
+enum ShortLongStatus short_long_stats(const uint8_t *ptr,
+                                      size_t len,
+                                      const struct ShortLongConfig *config,
+                                      struct ShortLongStats *stats_out);
+
+// Like [`short_long_stats`], but counts large texts on up to `threads`
+// threads, or one per CPU if `threads` is 0. The result is identical to the
+// serial one.
+//
+// # Safety
+//
+// Same as [`short_long_stats`].
+enum ShortLongStatus short_long_stats_parallel(const uint8_t *ptr,
+                                               size_t len,
+                                               const struct ShortLongConfig *config,
+                                               size_t threads,
+                                               struct ShortLongStats *stats_out);
+
+// Counts the words of the UTF-8 string at `ptr` by length, as
+// [`histogram`](crate::histogram) does, into the `buckets` entries at
+// `counts_out`. The last entry is the overflow bucket for every word at
+// least `buckets - 1` long. `counts_out` is overwritten, not added to.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], and `counts_out` must be
+// null or valid for writing `buckets` values.
+enum ShortLongStatus short_long_histogram(const uint8_t *ptr,
+                                          size_t len,
+                                          const struct ShortLongConfig *config,
+                                          uint64_t *counts_out,
+                                          size_t buckets);
+
+// Returns the default [`DiversityOptions`]: case folded, a 50-word MATTR
+// window, an MTLD threshold of 0.72 and 42-word HD-D samples.
+struct ShortLongDiversityOptions short_long_default_diversity_options(void);
+
+// Measures the lexical diversity of the UTF-8 string at `ptr`, as
+// [`diversity`](crate::diversity) does, and writes it to `diversity_out`.
+// A null `config` or `options` selects the default. Returns
+// [`ShortLongStatus::EmptyInput`] without writing anything if the text has
+// no words, or [`ShortLongStatus::InvalidArgument`] if the MTLD threshold
+// is NaN.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], `options` must be null or
+// point to valid [`ShortLongDiversityOptions`], and `diversity_out` must
+// be null or valid for writes.
+enum ShortLongStatus short_long_diversity(const uint8_t *ptr,
+                                          size_t len,
+                                          const struct ShortLongConfig *config,
+                                          const struct ShortLongDiversityOptions *options,
+                                          struct ShortLongDiversity *diversity_out);
+
+// Splits the UTF-8 string at `ptr` into sentences, as
+// [`sentences`](crate::sentences) does, writing the byte span of each to
+// `spans_out` and their number to `count_out`. If `capacity` is too small,
+// nothing is written to `spans_out`, the number of sentences is still
+// written to `count_out`, and [`ShortLongStatus::BufferTooSmall`] is
+// returned.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `spans_out` must be
+// null or valid for writing `capacity` spans, and `count_out` must be null
+// or valid for writes.
+enum ShortLongStatus short_long_sentences(const uint8_t *ptr,
+                                          size_t len,
+                                          struct ShortLongSpan *spans_out,
+                                          size_t capacity,
+                                          size_t *count_out);
+
+// Counts each sentence of the UTF-8 string at `ptr`, as
+// [`sentence_stats`](crate::sentence_stats) does, writing one
+// [`ShortLongStats`] per sentence to `stats_out` and their number to
+// `count_out`. A sentence with no words left after removing stopwords has
+// a NaN ratio. If `capacity` is too small, nothing is written to
+// `stats_out`, the number of sentences is still written to `count_out`,
+// and [`ShortLongStatus::BufferTooSmall`] is returned.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], `stats_out` must be null or
+// valid for writing `capacity` stats, and `count_out` must be null or valid
+// for writes.
+enum ShortLongStatus short_long_sentence_stats(const uint8_t *ptr,
+                                               size_t len,
+                                               const struct ShortLongConfig *config,
+                                               struct ShortLongStats *stats_out,
+                                               size_t capacity,
+                                               size_t *count_out);
+
+// Scores `count` documents in one call, writing the ratio and status of
+// `docs[i]` to `results_out[i]` as [`short_long_result`] would.
+//
+// The returned status covers the call as a whole: [`ShortLongStatus::Ok`]
+// means every result was written, even if some documents failed on their
+// own.
+//
+// # Safety
+//
+// `docs` must be null or point to `count` documents, each of which is
+// either null with length 0 or points to `len` readable bytes. `config`
+// must be null or point to a valid [`ShortLongConfig`], and `results_out`
+// must be null or valid for writing `count` results.
+enum ShortLongStatus short_long_batch(const struct ShortLongDocument *docs,
+                                      size_t count,
+                                      const struct ShortLongConfig *config,
+                                      struct ShortLongResult *results_out);
+
+// Like [`short_long_batch`], but spreads the documents over up to
+// `threads` threads, or one per CPU if `threads` is 0.
+//
+// # Safety
+//
+// Same as [`short_long_batch`].
+enum ShortLongStatus short_long_batch_parallel(const struct ShortLongDocument *docs,
+                                               size_t count,
+                                               const struct ShortLongConfig *config,
+                                               size_t threads,
+                                               struct ShortLongResult *results_out);
+
+// Like [`short_long_batch`], but with the `count` documents concatenated
+// in one buffer: document `i` is the bytes from `offsets[i]` up to
+// `offsets[i + 1]`, so `offsets` has `count + 1` entries. Returns
+// [`ShortLongStatus::InvalidArgument`] without writing any results if the
+// offsets decrease or run past `len`, or if `count + 1` overflows.
+//
+// # Safety
+//
+// `buf` must be null or point to `len` readable bytes, `offsets` must point
+// to `count + 1` values, `config` must be null or point to a valid
+// [`ShortLongConfig`], and `results_out` must be null or valid for writing
+// `count` results.
+enum ShortLongStatus short_long_batch_offsets(const uint8_t *buf,
+                                              size_t len,
+                                              const size_t *offsets,
+                                              size_t count,
+                                              const struct ShortLongConfig *config,
+                                              struct ShortLongResult *results_out);
+
+// Like [`short_long_batch_offsets`], but spreads the documents over up to
+// `threads` threads, or one per CPU if `threads` is 0.
+//
+// # Safety
+//
+// Same as [`short_long_batch_offsets`].
+enum ShortLongStatus short_long_batch_offsets_parallel(const uint8_t *buf,
+                                                       size_t len,
+                                                       const size_t *offsets,
+                                                       size_t count,
+                                                       const struct ShortLongConfig *config,
+                                                       size_t threads,
+                                                       struct ShortLongResult *results_out);
+
+// Adds the counts of `a` and `b` and writes the sum, with its ratio
+// recomputed, to `out`, which may be the same as `a` or `b`. The ratios of
+// `a` and `b` are ignored. Returns [`ShortLongStatus::InvalidArgument`]
+// without writing anything if a count overflows.
+//
+// # Safety
+//
+// `a` and `b` must be null or point to valid [`ShortLongStats`], and `out`
+// must be null or valid for writes.
+enum ShortLongStatus short_long_stats_merge(const struct ShortLongStats *a,
+                                            const struct ShortLongStats *b,
+                                            struct ShortLongStats *out);
+
+// Serializes the counts of `stats` compactly into `buf_out` for sending to
+// another process, as [`Stats::encode`] does, and writes the number of
+// bytes used to `len_out`. If `capacity` is too small, nothing is written
+// to `buf_out`, the required size is written to `len_out`, and
+// [`ShortLongStatus::BufferTooSmall`] is returned. A buffer of
+// `SHORT_LONG_STATS_MAX_ENCODED_LEN` bytes is always large enough.
+//
+// # Safety
+//
+// `stats` must be null or point to valid [`ShortLongStats`], `buf_out`
+// must be null or valid for writing `capacity` bytes, and `len_out` must be
+// null or valid for writes.
+enum ShortLongStatus short_long_stats_encode(const struct ShortLongStats *stats,
+                                             uint8_t *buf_out,
+                                             size_t capacity,
+                                             size_t *len_out);
+
+// Parses `len` bytes written by [`short_long_stats_encode`] into
+// `stats_out`, with the ratio recomputed. Returns
+// [`ShortLongStatus::InvalidArgument`] if the bytes are not a valid
+// encoding.
+//
+// # Safety
+//
+// `buf` must be null or point to `len` readable bytes, and `stats_out`
+// must be null or valid for writes.
+enum ShortLongStatus short_long_stats_decode(const uint8_t *buf,
+                                             size_t len,
+                                             struct ShortLongStats *stats_out);
+
+// Starts counting a text that will be fed in chunks with
+// [`short_long_state_feed`]. Returns null if the stopwords in `config` are
+// not UTF-8. The handle must be released with [`short_long_state_free`].
+//
+// # Safety
+//
+// `config` must be null or point to a valid [`ShortLongConfig`].
+struct ShortLongState *short_long_state_new(const struct ShortLongConfig *config);
+
+// Counts the next `len` bytes of the text. A word or UTF-8 character may
+// continue in the next chunk.
+//
+// If the text is not UTF-8, [`ShortLongStatus::InvalidUtf8`] is returned,
+// the offset of the first invalid sequence from the start of the whole
+// text is written to `error_offset_out`, and the handle should only be
+// freed.
+//
+// # Safety
+//
+// `state` must be null or a live handle from [`short_long_state_new`],
+// `ptr` must be null or point to `len` readable bytes, and
+// `error_offset_out` must be null or valid for writes.
+enum ShortLongStatus short_long_state_feed(struct ShortLongState *state,
+                                           const uint8_t *ptr,
+                                           size_t len,
+                                           size_t *error_offset_out);
+
+// Counts the words held back at the end of the text and writes the totals
+// for everything fed to `stats_out`, like [`short_long_stats`] on the whole
+// text. The handle is then reset, ready to count a new text with the same
+// config.
+//
+// # Safety
+//
+// `state` must be null or a live handle from [`short_long_state_new`], and
+// `stats_out` and `error_offset_out` must be null or valid for writes.
+enum ShortLongStatus short_long_state_finish(struct ShortLongState *state,
+                                             struct ShortLongStats *stats_out,
+                                             size_t *error_offset_out);
+
+// Releases a handle from [`short_long_state_new`]. Null is ignored.
+//
+// # Safety
+//
+// `state` must be null or a live handle from [`short_long_state_new`],
+// which must not be used afterwards.
+void short_long_state_free(struct ShortLongState *state);
+
+// Finds the `k` most frequent words of the UTF-8 string at `ptr`, as
+// [`top_words`](crate::top_words) does, and writes them to `result_out`.
+// On success the words must be released with
+// [`short_long_top_words_free`]; on failure nothing is written.
+//
+// # Safety
+//
+// `ptr` must be null or point to `len` readable bytes, `config` must be
+// null or point to a valid [`ShortLongConfig`], and `result_out` must be
+// valid for writes.
+enum ShortLongStatus short_long_top_words(const uint8_t *ptr,
+                                          size_t len,
+                                          const struct ShortLongConfig *config,
+                                          size_t k,
+                                          struct ShortLongTopWords *result_out);
+
+// Releases the words of a [`ShortLongTopWords`] filled by
+// [`short_long_top_words`] and resets it to empty, so freeing it twice is
+// harmless. Null is ignored.
+//
+// # Safety
+//
+// `result` must be null or point to a [`ShortLongTopWords`] that is empty
+// or was filled by [`short_long_top_words`] and not changed since.
+void short_long_top_words_free(struct ShortLongTopWords *result);
+
+#endif  /* SHORT_LONG_H */
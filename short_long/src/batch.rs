@@ -0,0 +1,52 @@
+//! Scoring many documents in one call.
+
+use crate::{Config, Error};
+
+/// Computes the ratio of each UTF-8 document in `docs`, as
+/// [`ratio_bytes`](crate::ratio_bytes) would, in order.
+pub fn ratio_batch<D: AsRef<[u8]>>(docs: &[D], config: &Config) -> Vec<Result<f64, Error>> {
+    docs.iter()
+        .map(|doc| crate::ratio_bytes(doc.as_ref(), config))
+        .collect()
+}
+
+/// Splits `buf` into the documents between consecutive `offsets`, so that
+/// document `i` is `buf[offsets[i]..offsets[i + 1]]`. Returns `None` if the
+/// offsets decrease or run past the end of `buf`.
+pub fn split_offsets<'a>(buf: &'a [u8], offsets: &[usize]) -> Option<Vec<&'a [u8]>> {
+    offsets.windows(2).map(|w| buf.get(w[0]..w[1])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_result_per_document() {
+        let docs = ["tiny enormously", "the a", "caf\u{e9}"];
+        let mut docs: Vec<&[u8]> = docs.iter().map(|d| d.as_bytes()).collect();
+        docs.push(b"caf\xe9");
+        let results = ratio_batch(&docs, &Config::default());
+        assert_eq!(
+            results,
+            [
+                Ok(0.5),
+                Err(Error::EmptyInput),
+                Ok(0.0),
+                Err(Error::InvalidUtf8 { valid_up_to: 3 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn offsets_must_be_in_bounds_and_ordered() {
+        let buf = b"onetwothree";
+        assert_eq!(
+            split_offsets(buf, &[0, 3, 6, 11]),
+            Some(vec![&b"one"[..], b"two", b"three"])
+        );
+        assert_eq!(split_offsets(buf, &[0]), Some(vec![]));
+        assert_eq!(split_offsets(buf, &[3, 0]), None);
+        assert_eq!(split_offsets(buf, &[0, 12]), None);
+    }
+}
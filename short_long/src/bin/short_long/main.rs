@@ -0,0 +1,471 @@
+//! Command-line front end: prints the short/long ratio of files or standard
+//! input.
+//!
+//! Exits with 0 on success, 1 if any input could not be read or measured,
+//! and 2 on a usage error.
+
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use short_long::{stats_parallel, top_words, Config, Error};
+
+use crate::output::{Format, Output};
+
+mod output;
+
+const USAGE: &str = "\
+Usage: short_long [OPTIONS] [FILE]...
+
+Prints the fraction of words longer than the threshold in each FILE, or in
+standard input if no FILE is given or FILE is -. With --by line,
+--by paragraph or --by sentence, prints one record per line,
+blank-line-separated paragraph or sentence, tagged with its first line
+number, then a summary of their ratios. Pieces with no words left are
+skipped. With --top N, prints the N most frequent words of each record, and
+their counts, instead of its ratio.
+
+Options:
+  -t, --threshold N      words longer than N units are long [default: 8]
+  -s, --stopwords LIST   comma-separated words to ignore [default: the,a]
+  -i, --ignore-case      match stopwords regardless of case
+  -u, --unit UNIT        bytes, chars or graphemes [default: chars]
+      --tokenizer NAME   python or legacy [default: python]
+  -b, --by SCOPE         file, line, paragraph or sentence [default: file]
+  -f, --format FORMAT    text, ratio, json, ndjson or csv [default: text]
+  -r, --readability      add readability indices to text output
+      --top N            print the N most frequent words instead
+      --lossy            replace invalid UTF-8 instead of failing
+  -h, --help             print this help
+  -V, --version          print the version
+";
+
+/// Exit code for unreadable or unmeasurable input.
+const INVALID_INPUT: u8 = 1;
+/// Exit code for bad command-line arguments.
+const USAGE_ERROR: u8 = 2;
+
+/// What each printed record covers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scope {
+    File,
+    Line,
+    /// A run of lines ended by a line holding only whitespace.
+    Paragraph,
+    /// A sentence, as [`short_long::sentences`] finds them.
+    Sentence,
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug, PartialEq)]
+struct Args {
+    config: Config,
+    format: Format,
+    scope: Scope,
+    readability: bool,
+    /// How many frequent words to print instead of the ratio, if any.
+    top: Option<usize>,
+    lossy: bool,
+    /// Inputs to measure; `-` is standard input.
+    files: Vec<String>,
+}
+
+/// What the command line asks for.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Args),
+    Help,
+    Version,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut parsed = Args {
+        config: Config::default(),
+        format: Format::Text,
+        scope: Scope::File,
+        readability: false,
+        top: None,
+        lossy: false,
+        files: Vec::new(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            parsed.files.extend(args.by_ref());
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            parsed.files.push(arg);
+            continue;
+        }
+        // Accept both `--name value` and `--name=value`.
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if inline.is_some()
+            && matches!(
+                flag,
+                "--ignore-case" | "--readability" | "--lossy" | "--help" | "--version"
+            )
+        {
+            return Err(format!("{} takes no value", flag));
+        }
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        match flag {
+            "-t" | "--threshold" => {
+                let value = value()?;
+                parsed.config.threshold = value
+                    .parse()
+                    .map_err(|_| format!("invalid threshold {:?}", value))?;
+            }
+            "-s" | "--stopwords" => {
+                parsed.config.stopwords = value()?
+                    .split(',')
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "-u" | "--unit" => {
+                parsed.config.unit = value()?.parse().map_err(|e| format!("{}", e))?;
+            }
+            "--tokenizer" => {
+                parsed.config.tokenizer = value()?.parse().map_err(|e| format!("{}", e))?;
+            }
+            "-b" | "--by" => {
+                parsed.scope = match value()?.as_str() {
+                    "file" => Scope::File,
+                    "line" => Scope::Line,
+                    "paragraph" => Scope::Paragraph,
+                    "sentence" => Scope::Sentence,
+                    other => return Err(format!("unknown scope {:?}", other)),
+                };
+            }
+            "-f" | "--format" => {
+                let value = value()?;
+                parsed.format =
+                    Format::parse(&value).ok_or_else(|| format!("unknown format {:?}", value))?;
+            }
+            "--top" => {
+                let value = value()?;
+                parsed.top = match value.parse() {
+                    Ok(0) | Err(_) => return Err(format!("invalid word count {:?}", value)),
+                    Ok(k) => Some(k),
+                };
+            }
+            "-i" | "--ignore-case" => parsed.config.case_sensitive = false,
+            "-r" | "--readability" => parsed.readability = true,
+            "--lossy" => parsed.lossy = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    if parsed.files.is_empty() {
+        parsed.files.push("-".to_string());
+    }
+    Ok(Command::Run(parsed))
+}
+
+/// Reads all of `path`, or standard input for `-`.
+fn read_input(path: &str) -> io::Result<Vec<u8>> {
+    if path == "-" {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    } else {
+        fs::read(path)
+    }
+}
+
+/// Reads one input as text.
+fn read_text(path: &str, lossy: bool) -> Result<String, String> {
+    let bytes = read_input(path).map_err(|e| e.to_string())?;
+    if lossy {
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    } else {
+        String::from_utf8(bytes).map_err(|e| Error::from(e.utf8_error()).to_string())
+    }
+}
+
+/// A line, paragraph or sentence of an input.
+#[derive(Debug, PartialEq)]
+struct Segment<'a> {
+    /// 1-based number of its first line.
+    line: usize,
+    text: &'a str,
+}
+
+/// Splits `text` into lines, paragraphs or sentences. Blank lines are not
+/// part of any paragraph.
+fn segments(text: &str, scope: Scope) -> Vec<Segment<'_>> {
+    // Lines and sentences are subslices of `text`, so a paragraph is the
+    // span from its first line's start to its last line's end.
+    let offset = |piece: &str| piece.as_ptr() as usize - text.as_ptr() as usize;
+    if scope == Scope::Sentence {
+        let (mut line, mut counted) = (1, 0);
+        return short_long::sentences(text)
+            .map(|sentence| {
+                let start = offset(sentence);
+                line += text[counted..start].matches('\n').count();
+                counted = start;
+                Segment {
+                    line,
+                    text: sentence,
+                }
+            })
+            .collect();
+    }
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Segment { line: i + 1, text });
+    if scope == Scope::Line {
+        return lines.collect();
+    }
+    let mut paragraphs = Vec::new();
+    let mut current: Option<(usize, usize, usize)> = None;
+    for Segment {
+        line,
+        text: line_text,
+    } in lines
+    {
+        if line_text.trim().is_empty() {
+            paragraphs.extend(current.take());
+        } else {
+            let end = offset(line_text) + line_text.len();
+            current.get_or_insert((line, offset(line_text), end)).2 = end;
+        }
+    }
+    paragraphs.extend(current);
+    paragraphs
+        .into_iter()
+        .map(|(line, start, end)| Segment {
+            line,
+            text: &text[start..end],
+        })
+        .collect()
+}
+
+/// The `k` most frequent words of `text`, or [`Error::EmptyInput`] if it has
+/// none.
+fn top<'a>(text: &'a str, config: &Config, k: usize) -> Result<Vec<(Cow<'a, str>, u64)>, Error> {
+    let words: Vec<_> = top_words(text, config, k).collect();
+    if words.is_empty() {
+        Err(Error::EmptyInput)
+    } else {
+        Ok(words)
+    }
+}
+
+/// Measures every input, printing records as it goes. Returns whether
+/// every input could be measured.
+fn run(args: &Args, output: &mut Output<impl Write>) -> io::Result<bool> {
+    let mut ok = true;
+    for path in &args.files {
+        let text = match read_text(path, args.lossy) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("short_long: {}: {}", path, e);
+                ok = false;
+                continue;
+            }
+        };
+        if args.scope == Scope::File {
+            let printed = match args.top {
+                Some(k) => {
+                    top(&text, &args.config, k).map(|words| output.words(path, None, &words))
+                }
+                None => {
+                    let stats = stats_parallel(&text, &args.config, 0);
+                    let ratio = stats.ratio();
+                    ratio.map(|ratio| output.record(path, None, &stats, ratio))
+                }
+            };
+            match printed {
+                Ok(printed) => printed?,
+                Err(e) => {
+                    eprintln!("short_long: {}: {}", path, e);
+                    ok = false;
+                }
+            }
+            continue;
+        }
+        for segment in segments(&text, args.scope) {
+            let line = Some(segment.line);
+            if let Some(k) = args.top {
+                if let Ok(words) = top(segment.text, &args.config, k) {
+                    output.words(path, line, &words)?;
+                }
+                continue;
+            }
+            let mut stats = short_long::stats(segment.text, &args.config);
+            if args.scope == Scope::Sentence {
+                // As in `sentence_stats`, each record is one sentence.
+                stats.sentences = 1;
+            }
+            if let Ok(ratio) = stats.ratio() {
+                output.record(path, line, &stats, ratio)?;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    match parse_args(env::args().skip(1)) {
+        Ok(Command::Run(args)) => {
+            let noun = match args.scope {
+                Scope::File => None,
+                Scope::Line => Some("lines"),
+                Scope::Paragraph => Some("paragraphs"),
+                Scope::Sentence => Some("sentences"),
+            };
+            let total = args.scope == Scope::File && args.files.len() > 1;
+            let out = BufWriter::new(io::stdout().lock());
+            let output = match args.top {
+                Some(_) => Output::top_words(out, args.format),
+                None => Output::new(out, args.format, args.readability),
+            };
+            let result = output.and_then(|mut output| {
+                let ok = run(&args, &mut output)?;
+                output.finish(total, noun)?;
+                Ok(ok)
+            });
+            match result {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::from(INVALID_INPUT),
+                // Stop quietly when the reader goes away, as with `| head`.
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("short_long: {}", e);
+                    ExitCode::from(INVALID_INPUT)
+                }
+            }
+        }
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!("short_long {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("short_long: {}\n\n{}", e, USAGE);
+            ExitCode::from(USAGE_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use short_long::LengthUnit;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn run_args(args: &[&str]) -> Args {
+        match parse(args) {
+            Ok(Command::Run(args)) => args,
+            other => panic!("expected arguments to run, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_read_stdin() {
+        let args = run_args(&[]);
+        assert_eq!(args.config, Config::default());
+        assert_eq!(args.format, Format::Text);
+        assert_eq!(args.files, ["-"]);
+    }
+
+    #[test]
+    fn options_set_config() {
+        let args = run_args(&[
+            "-t",
+            "5",
+            "--stopwords=of,and,",
+            "-i",
+            "--unit",
+            "bytes",
+            "--format=ratio",
+            "a.txt",
+            "--",
+            "-b.txt",
+        ]);
+        assert_eq!(args.config.threshold, 5);
+        assert_eq!(args.config.stopwords.len(), 2);
+        assert!(!args.config.case_sensitive);
+        assert_eq!(args.config.unit, LengthUnit::Bytes);
+        assert_eq!(args.format, Format::Ratio);
+        assert_eq!(args.files, ["a.txt", "-b.txt"]);
+
+        assert!(run_args(&["--stopwords", ""]).config.stopwords.is_empty());
+    }
+
+    #[test]
+    fn paragraphs_are_separated_by_blank_lines() {
+        let text = "\n one\ntwo\n  \n\nthree\r\n\r\nfour\n";
+        let lines: Vec<_> = segments(text, Scope::Line)
+            .into_iter()
+            .map(|s| (s.line, s.text))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (1, ""),
+                (2, " one"),
+                (3, "two"),
+                (4, "  "),
+                (5, ""),
+                (6, "three"),
+                (7, ""),
+                (8, "four")
+            ]
+        );
+        let paragraphs: Vec<_> = segments(text, Scope::Paragraph)
+            .into_iter()
+            .map(|s| (s.line, s.text))
+            .collect();
+        assert_eq!(paragraphs, [(2, " one\ntwo"), (6, "three"), (8, "four")]);
+    }
+
+    #[test]
+    fn sentences_are_tagged_with_their_first_line() {
+        let text = "One. Two
+wraps. Three.
+
+Four";
+        let sentences: Vec<_> = segments(text, Scope::Sentence)
+            .into_iter()
+            .map(|s| (s.line, s.text))
+            .collect();
+        assert_eq!(
+            sentences,
+            [(1, "One."), (1, "Two\nwraps."), (2, "Three."), (4, "Four")]
+        );
+    }
+
+    #[test]
+    fn bad_usage_is_an_error() {
+        assert_eq!(parse(&["-h", "x"]), Ok(Command::Help));
+        assert!(parse(&["--threshold"]).is_err());
+        assert!(parse(&["--threshold", "-1"]).is_err());
+        assert!(parse(&["--unit", "words"]).is_err());
+        assert!(parse(&["--lossy=yes"]).is_err());
+        assert!(parse(&["--by", "word"]).is_err());
+        assert!(parse(&["--top", "0"]).is_err());
+        assert_eq!(run_args(&["--top=5"]).top, Some(5));
+        assert!(parse(&["--bogus"]).is_err());
+    }
+}
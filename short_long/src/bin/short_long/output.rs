@@ -0,0 +1,534 @@
+//! Printing records in each output format.
+//!
+//! Every structured format shares one field schema, in this order:
+//!
+//! | field             | type            | meaning                                   |
+//! |-------------------|-----------------|-------------------------------------------|
+//! | `file`            | string          | input path, `-` for standard input        |
+//! | `line`            | integer or null | first line of the record; null per file   |
+//! | `total_tokens`    | integer         | words before stopwords are removed        |
+//! | `filtered_tokens` | integer         | words after stopwords are removed         |
+//! | `long_tokens`     | integer         | filtered words longer than the threshold  |
+//! | `total_chars`     | integer         | length of the filtered words, in the unit |
+//! | `ratio`           | number          | `long_tokens / filtered_tokens`           |
+//! | `words`           | integer         | tokens containing a letter or digit       |
+//! | `sentences`       | integer         | words that end a sentence                 |
+//! | `syllables`       | integer         | estimated syllables in `words`            |
+//! | `polysyllables`   | integer         | words of three or more syllables          |
+//! | `letters`         | integer         | letters and digits in `words`             |
+//! | `flesch_reading_ease`, `flesch_kincaid_grade`, `gunning_fog`, `smog`, `coleman_liau`, `automated_readability` | number or null | readability indices; null without `words` |
+//! | `polysyllabic_ratio` | number or null | `polysyllables / words`               |
+//! | `words_per_sentence` | number or null | `words / sentences`, at least one sentence |
+//!
+//! Fields are only ever added, at the end. The README documents the same
+//! schema.
+//!
+//! With `--top`, each record is instead one row per frequent word, with the
+//! fields `file`, `line`, `rank` (from 1), `word` and `count`.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use short_long::{Readability, Stats, Summary};
+
+/// How records are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// The ratio and the counts behind it, with the record's position.
+    Text,
+    /// The ratio alone, at full precision.
+    Ratio,
+    /// One pretty-printed JSON document holding every record, their total
+    /// and a summary of their ratios.
+    Json,
+    /// One compact JSON object per record, per line.
+    Ndjson,
+    /// A header row, then one row per record.
+    Csv,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "ratio" => Some(Format::Ratio),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// A value of one field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Value<'a> {
+    Null,
+    Int(u64),
+    Float(f64),
+    Str(&'a str),
+}
+
+impl Value<'_> {
+    fn write_json(self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Value::Null => write!(out, "null"),
+            Value::Int(n) => write!(out, "{}", n),
+            Value::Float(x) if x.is_finite() => write!(out, "{}", x),
+            Value::Float(_) => write!(out, "null"),
+            Value::Str(s) => {
+                write!(out, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(out, "\\\"")?,
+                        '\\' => write!(out, "\\\\")?,
+                        '\n' => write!(out, "\\n")?,
+                        '\r' => write!(out, "\\r")?,
+                        '\t' => write!(out, "\\t")?,
+                        c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+                        c => write!(out, "{}", c)?,
+                    }
+                }
+                write!(out, "\"")
+            }
+        }
+    }
+
+    fn write_csv(self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Value::Null => Ok(()),
+            Value::Int(n) => write!(out, "{}", n),
+            Value::Float(x) => write!(out, "{}", x),
+            Value::Str(s) if s.contains(['"', ',', '\n', '\r']) => {
+                write!(out, "\"{}\"", s.replace('"', "\"\""))
+            }
+            Value::Str(s) => write!(out, "{}", s),
+        }
+    }
+}
+
+/// Names of the fields of a record, in schema order.
+const RECORD_FIELDS: [&str; 20] = [
+    "file",
+    "line",
+    "total_tokens",
+    "filtered_tokens",
+    "long_tokens",
+    "total_chars",
+    "ratio",
+    "words",
+    "sentences",
+    "syllables",
+    "polysyllables",
+    "letters",
+    "flesch_reading_ease",
+    "flesch_kincaid_grade",
+    "gunning_fog",
+    "smog",
+    "coleman_liau",
+    "automated_readability",
+    "polysyllabic_ratio",
+    "words_per_sentence",
+];
+
+/// Names of the fields of a `--top` row, in order.
+const TOP_FIELDS: [&str; 5] = ["file", "line", "rank", "word", "count"];
+
+fn record_values<'a>(
+    file: &'a str,
+    line: Option<usize>,
+    stats: &Stats,
+    ratio: f64,
+) -> [Value<'a>; 20] {
+    let r = stats.readability().ok();
+    let index = |f: fn(&Readability) -> f64| r.as_ref().map_or(Value::Null, |r| Value::Float(f(r)));
+    [
+        Value::Str(file),
+        line.map_or(Value::Null, |line| Value::Int(line as u64)),
+        Value::Int(stats.total_tokens),
+        Value::Int(stats.filtered_tokens),
+        Value::Int(stats.long_tokens),
+        Value::Int(stats.total_chars),
+        Value::Float(ratio),
+        Value::Int(stats.words),
+        Value::Int(stats.sentences),
+        Value::Int(stats.syllables),
+        Value::Int(stats.polysyllables),
+        Value::Int(stats.letters),
+        index(|r| r.flesch_reading_ease),
+        index(|r| r.flesch_kincaid_grade),
+        index(|r| r.gunning_fog),
+        index(|r| r.smog),
+        index(|r| r.coleman_liau),
+        index(|r| r.automated_readability),
+        stats.polysyllabic_ratio().map_or(Value::Null, Value::Float),
+        stats.words_per_sentence().map_or(Value::Null, Value::Float),
+    ]
+}
+
+/// Writes `fields` as a JSON object, one field per line indented by
+/// `indent` spaces if it is `Some`, or all on one line otherwise.
+fn write_object<'a>(
+    out: &mut impl Write,
+    fields: impl IntoIterator<Item = (&'a str, Value<'a>)>,
+    indent: Option<usize>,
+) -> io::Result<()> {
+    write!(out, "{{")?;
+    for (i, (name, value)) in fields.into_iter().enumerate() {
+        match (i, indent) {
+            (0, None) => {}
+            (_, None) => write!(out, ", ")?,
+            (0, Some(n)) => write!(out, "\n{:n$}", "", n = n + 2)?,
+            (_, Some(n)) => write!(out, ",\n{:n$}", "", n = n + 2)?,
+        }
+        Value::Str(name).write_json(out)?;
+        write!(out, ": ")?;
+        value.write_json(out)?;
+    }
+    match indent {
+        Some(n) => write!(out, "\n{:n$}}}", ""),
+        None => write!(out, "}}"),
+    }
+}
+
+/// Prints records as they are measured, then whatever closes the format.
+pub struct Output<W: Write> {
+    out: W,
+    format: Format,
+    /// Whether text records also show the readability indices.
+    readability: bool,
+    /// Whether records are frequent words rather than ratios.
+    top: bool,
+    /// Rows written so far, in the structured formats.
+    records: usize,
+    total: Stats,
+    ratios: Vec<f64>,
+}
+
+impl<W: Write> Output<W> {
+    /// Writes anything that precedes the first record. Text records include
+    /// the readability indices if `readability` is set; the structured
+    /// formats always do.
+    pub fn new(out: W, format: Format, readability: bool) -> io::Result<Output<W>> {
+        Output::start(out, format, readability, false)
+    }
+
+    /// Like [`Output::new`], but for printing frequent words with
+    /// [`Output::words`] instead of ratios.
+    pub fn top_words(out: W, format: Format) -> io::Result<Output<W>> {
+        Output::start(out, format, false, true)
+    }
+
+    fn start(mut out: W, format: Format, readability: bool, top: bool) -> io::Result<Output<W>> {
+        let fields: &[&str] = if top { &TOP_FIELDS } else { &RECORD_FIELDS };
+        match format {
+            Format::Json => write!(out, "{{\n  \"records\": [")?,
+            Format::Csv => {
+                for (i, name) in fields.iter().enumerate() {
+                    write!(out, "{}{}", if i == 0 { "" } else { "," }, name)?;
+                }
+                writeln!(out)?;
+            }
+            _ => {}
+        }
+        Ok(Output {
+            out,
+            format,
+            readability,
+            top,
+            records: 0,
+            total: Stats::default(),
+            ratios: Vec::new(),
+        })
+    }
+
+    /// Prints the record for `file`, or for the part of it starting at
+    /// `line`, whose counts give `ratio`.
+    pub fn record(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        stats: &Stats,
+        ratio: f64,
+    ) -> io::Result<()> {
+        let values = record_values(file, line, stats, ratio);
+        let out = &mut self.out;
+        match self.format {
+            Format::Text => {
+                match line {
+                    Some(line) => write!(out, "{}:{}", file, line)?,
+                    None => write!(out, "{}", file)?,
+                }
+                write!(
+                    out,
+                    ": {:.4} ({} of {} words long)",
+                    ratio, stats.long_tokens, stats.filtered_tokens
+                )?;
+                match stats.readability() {
+                    Ok(r) if self.readability => writeln!(
+                        out,
+                        ", Flesch {:.1}, Flesch-Kincaid {:.1}, Fog {:.1}, SMOG {:.1}, \
+                         Coleman-Liau {:.1}, ARI {:.1}",
+                        r.flesch_reading_ease,
+                        r.flesch_kincaid_grade,
+                        r.gunning_fog,
+                        r.smog,
+                        r.coleman_liau,
+                        r.automated_readability
+                    )?,
+                    _ => writeln!(out)?,
+                }
+            }
+            Format::Ratio => writeln!(out, "{}", ratio)?,
+            _ => self.row(RECORD_FIELDS.into_iter().zip(values))?,
+        }
+        self.total += *stats;
+        self.ratios.push(ratio);
+        Ok(())
+    }
+
+    /// Prints the most frequent `words` of `file`, or of the part of it
+    /// starting at `line`, with their counts. Text puts them on one line
+    /// after the position, and ratio format prints just one word and its
+    /// count per line.
+    pub fn words(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        words: &[(Cow<str>, u64)],
+    ) -> io::Result<()> {
+        let out = &mut self.out;
+        match self.format {
+            Format::Text => {
+                match line {
+                    Some(line) => write!(out, "{}:{}:", file, line)?,
+                    None => write!(out, "{}:", file)?,
+                }
+                for (i, (word, count)) in words.iter().enumerate() {
+                    write!(out, "{} {} {}", if i == 0 { "" } else { "," }, word, count)?;
+                }
+                writeln!(out)?;
+            }
+            Format::Ratio => {
+                for (word, count) in words {
+                    writeln!(out, "{} {}", word, count)?;
+                }
+            }
+            _ => {
+                for (rank, (word, count)) in words.iter().enumerate() {
+                    let values = [
+                        Value::Str(file),
+                        line.map_or(Value::Null, |line| Value::Int(line as u64)),
+                        Value::Int(rank as u64 + 1),
+                        Value::Str(word),
+                        Value::Int(*count),
+                    ];
+                    self.row(TOP_FIELDS.into_iter().zip(values))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes one row of a structured format.
+    fn row<'a>(
+        &mut self,
+        fields: impl IntoIterator<Item = (&'a str, Value<'a>)>,
+    ) -> io::Result<()> {
+        let out = &mut self.out;
+        match self.format {
+            Format::Json => {
+                write!(out, "{}\n    ", if self.records == 0 { "" } else { "," })?;
+                write_object(out, fields, Some(4))?;
+            }
+            Format::Ndjson => {
+                write_object(out, fields, None)?;
+                writeln!(out)?;
+            }
+            Format::Csv => {
+                for (i, (_, value)) in fields.into_iter().enumerate() {
+                    if i > 0 {
+                        write!(out, ",")?;
+                    }
+                    value.write_csv(out)?;
+                }
+                writeln!(out)?;
+            }
+            Format::Text | Format::Ratio => unreachable!("not a structured format"),
+        }
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Writes what follows the records. In text format, that is a total
+    /// line if `total` is set, or a summary of the ratios of the records if
+    /// `noun` names them. Frequent words are followed by nothing but the
+    /// end of the JSON document.
+    pub fn finish(mut self, total: bool, noun: Option<&str>) -> io::Result<()> {
+        let summary = Summary::of(self.ratios);
+        let out = &mut self.out;
+        match self.format {
+            // Frequent words have no total or summary.
+            Format::Json if self.top => {
+                writeln!(out, "{}]\n}}", if self.records == 0 { "" } else { "\n  " })?;
+            }
+            _ if self.top => {}
+            Format::Text if total => {
+                if let Ok(ratio) = self.total.ratio() {
+                    writeln!(
+                        out,
+                        "total: {:.4} ({} of {} words long)",
+                        ratio, self.total.long_tokens, self.total.filtered_tokens
+                    )?;
+                }
+            }
+            Format::Text => {
+                if let (Some(s), Some(noun)) = (summary, noun) {
+                    writeln!(
+                        out,
+                        "summary: {} {}, mean {:.4}, min {:.4}, p10 {:.4}, p25 {:.4}, \
+                         median {:.4}, p75 {:.4}, p90 {:.4}, max {:.4}",
+                        s.count(),
+                        noun,
+                        s.mean(),
+                        s.min(),
+                        s.percentile(10.0),
+                        s.percentile(25.0),
+                        s.median(),
+                        s.percentile(75.0),
+                        s.percentile(90.0),
+                        s.max(),
+                    )?;
+                }
+            }
+            Format::Json => {
+                write!(
+                    out,
+                    "{}],\n  \"total\": ",
+                    if self.records == 0 { "" } else { "\n  " }
+                )?;
+                match self.total.ratio() {
+                    Ok(ratio) => {
+                        let values = record_values("", None, &self.total, ratio);
+                        let fields = RECORD_FIELDS.into_iter().zip(values).skip(2);
+                        write_object(out, fields, Some(2))?;
+                    }
+                    Err(_) => write!(out, "null")?,
+                }
+                write!(out, ",\n  \"summary\": ")?;
+                match summary {
+                    Some(s) => write_object(out, summary_fields(&s), Some(2))?,
+                    None => write!(out, "null")?,
+                }
+                writeln!(out, "\n}}")?;
+            }
+            Format::Ratio | Format::Ndjson | Format::Csv => {}
+        }
+        self.out.flush()
+    }
+}
+
+fn summary_fields(s: &Summary) -> [(&'static str, Value<'static>); 9] {
+    [
+        ("count", Value::Int(s.count() as u64)),
+        ("mean", Value::Float(s.mean())),
+        ("min", Value::Float(s.min())),
+        ("p10", Value::Float(s.percentile(10.0))),
+        ("p25", Value::Float(s.percentile(25.0))),
+        ("median", Value::Float(s.median())),
+        ("p75", Value::Float(s.percentile(75.0))),
+        ("p90", Value::Float(s.percentile(90.0))),
+        ("max", Value::Float(s.max())),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: Format, records: &[(&str, Option<usize>, &str)]) -> String {
+        let mut buf = Vec::new();
+        let mut output = Output::new(&mut buf, format, false).unwrap();
+        for &(file, line, text) in records {
+            let stats = short_long::stats(text, &Default::default());
+            if let Ok(ratio) = stats.ratio() {
+                output.record(file, line, &stats, ratio).unwrap();
+            }
+        }
+        output.finish(false, Some("lines")).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    const RECORDS: &[(&str, Option<usize>, &str)] = &[
+        ("a \"b\",c", Some(3), "one extraordinary"),
+        ("d", None, "the"),
+        ("d", None, "two"),
+    ];
+
+    #[test]
+    fn csv_quotes_fields() {
+        let csv = render(Format::Csv, RECORDS);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], RECORD_FIELDS.join(","));
+        assert!(lines[1].starts_with("\"a \"\"b\"\",c\",3,2,2,1,16,0.5,2,0,6,1,16,"));
+        assert!(lines[2].starts_with("d,,1,1,0,3,0,1,0,1,0,3,"));
+        assert_eq!(lines[2].split(',').count(), RECORD_FIELDS.len());
+    }
+
+    #[test]
+    fn ndjson_is_one_object_per_line() {
+        let ndjson = render(Format::Ndjson, RECORDS);
+        let lines: Vec<_> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(
+            "{\"file\": \"a \\\"b\\\",c\", \"line\": 3, \"total_tokens\": 2, \"filtered_tokens\": 2, \
+             \"long_tokens\": 1, \"total_chars\": 16, \"ratio\": 0.5, \"words\": 2, \"sentences\": 0, "
+        ));
+        assert!(lines[1].starts_with("{\"file\": \"d\", \"line\": null, "));
+        assert!(lines[1].contains(", \"smog\": 3.1291, \"coleman_liau\": "));
+    }
+
+    fn render_top(format: Format) -> String {
+        let mut buf = Vec::new();
+        let mut output = Output::top_words(&mut buf, format).unwrap();
+        let words = [(Cow::from("dog"), 3), (Cow::from("a,b"), 1)];
+        output.words("f", None, &words).unwrap();
+        output.words("f", Some(2), &words[..1]).unwrap();
+        output.finish(false, Some("lines")).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn top_words_are_one_row_per_word() {
+        assert_eq!(render_top(Format::Text), "f: dog 3, a,b 1\nf:2: dog 3\n");
+        assert_eq!(render_top(Format::Ratio), "dog 3\na,b 1\ndog 3\n");
+        assert_eq!(
+            render_top(Format::Csv),
+            "file,line,rank,word,count\nf,,1,dog,3\nf,,2,\"a,b\",1\nf,2,1,dog,3\n"
+        );
+        let ndjson = render_top(Format::Ndjson);
+        assert_eq!(
+            ndjson.lines().nth(2),
+            Some("{\"file\": \"f\", \"line\": 2, \"rank\": 1, \"word\": \"dog\", \"count\": 3}")
+        );
+        let json = render_top(Format::Json);
+        assert!(json.starts_with("{\n  \"records\": [\n    {\n      \"file\": \"f\",\n"));
+        assert!(json.ends_with("\"count\": 3\n    }\n  ]\n}\n"));
+    }
+
+    #[test]
+    fn json_holds_records_total_and_summary() {
+        let json = render(Format::Json, &RECORDS[..2]);
+        assert!(
+            json.starts_with("{\n  \"records\": [\n    {\n      \"file\": \"a \\\"b\\\",c\",\n")
+        );
+        assert!(json.contains("\n  ],\n  \"total\": {\n    \"total_tokens\": 2,\n"));
+        assert!(json.contains("\n  \"summary\": {\n    \"count\": 1,\n"));
+        assert!(json.ends_with("\n    \"max\": 0.5\n  }\n}\n"));
+        assert_eq!(
+            render(Format::Json, &[]),
+            "{\n  \"records\": [],\n  \"total\": null,\n  \"summary\": null\n}\n"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Tunable parameters for the short/long ratio.
+
+use std::borrow::Cow;
+use std::collections::{hash_set, HashSet};
+
+use crate::{LengthUnit, Tokenizer};
+
+/// Controls which words are counted and which of them count as long.
+///
+/// The [`Default`] preset matches `inPython.py`: it splits words like
+/// Python's `str.split()`, counts words longer than 8 characters as long,
+/// and ignores "the" and "a", matching case exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Words longer than this many [`unit`](Config::unit)s count as long.
+    pub threshold: usize,
+    /// What word lengths are measured in.
+    pub unit: LengthUnit,
+    /// Words that are left out of the ratio entirely.
+    pub stopwords: Stopwords,
+    /// Whether a word must match a stopword's case exactly to be ignored.
+    pub case_sensitive: bool,
+    /// How the text is split into words.
+    pub tokenizer: Tokenizer,
+}
+
+/// Stopword lists up to this long are scanned rather than hashed.
+const SCAN_LIMIT: usize = 8;
+
+impl Config {
+    /// Returns the default preset with a different long-word threshold.
+    pub fn with_threshold(threshold: usize) -> Config {
+        Config {
+            threshold,
+            ..Config::default()
+        }
+    }
+
+    /// Whether `word` should be left out of the ratio.
+    pub fn is_stopword(&self, word: &str) -> bool {
+        if self.case_sensitive {
+            // Comparing against a handful of words, most of which differ in
+            // length, is cheaper than hashing every word.
+            if self.stopwords.len() <= SCAN_LIMIT {
+                self.stopwords.iter().any(|s| s == word)
+            } else {
+                self.stopwords.contains(word)
+            }
+        } else {
+            self.stopwords.folded.contains(&*fold_case(word))
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            threshold: 8,
+            unit: LengthUnit::default(),
+            stopwords: ["the", "a"].iter().map(|s| s.to_string()).collect(),
+            case_sensitive: true,
+            tokenizer: Tokenizer::default(),
+        }
+    }
+}
+
+/// The set of [`Config::stopwords`].
+///
+/// Alongside the words themselves it keeps them lowercased, so that matching
+/// a word while ignoring case is one lookup rather than a scan of the set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stopwords {
+    words: HashSet<String>,
+    /// `words` with every character lowercased.
+    folded: HashSet<String>,
+}
+
+impl Stopwords {
+    pub fn new() -> Stopwords {
+        Stopwords::default()
+    }
+
+    /// Adds `word`, returning whether it was new.
+    pub fn insert(&mut self, word: String) -> bool {
+        self.folded.insert(fold_case(&word).into_owned());
+        self.words.insert(word)
+    }
+
+    /// Whether `word` is in the set, matching case exactly.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns an iterator over the words, in arbitrary order.
+    pub fn iter(&self) -> hash_set::Iter<'_, String> {
+        self.words.iter()
+    }
+}
+
+impl FromIterator<String> for Stopwords {
+    fn from_iter<I: IntoIterator<Item = String>>(words: I) -> Stopwords {
+        let mut stopwords = Stopwords::new();
+        stopwords.extend(words);
+        stopwords
+    }
+}
+
+impl Extend<String> for Stopwords {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, words: I) {
+        for word in words {
+            self.insert(word);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Stopwords {
+    type Item = &'a String;
+    type IntoIter = hash_set::Iter<'a, String>;
+
+    fn into_iter(self) -> hash_set::Iter<'a, String> {
+        self.iter()
+    }
+}
+
+/// Lowercases every character of `word`, borrowing it if none changes.
+fn fold_case(word: &str) -> Cow<'_, str> {
+    let unchanged = |c: char| {
+        let mut lower = c.to_lowercase();
+        lower.next() == Some(c) && lower.next().is_none()
+    };
+    if word.chars().all(unchanged) {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Owned(word.chars().flat_map(char::to_lowercase).collect())
+    }
+}
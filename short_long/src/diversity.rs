@@ -0,0 +1,352 @@
+//! Lexical diversity: how varied the vocabulary of a text is.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{Config, Error};
+
+/// Controls how [`diversity`] tells words apart and the parameters of its
+/// windowed measures.
+///
+/// The [`Default`] preset folds case and uses the parameters from McCarthy
+/// and Jarvis (2010): a 50-word MATTR window, an MTLD threshold of 0.72 and
+/// 42-word HD-D samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiversityOptions {
+    /// Whether words that differ only in case, such as "The" and "the",
+    /// count as different types.
+    pub case_sensitive: bool,
+    /// Length in words of the moving window for MATTR.
+    pub window: usize,
+    /// Type-token ratio at which MTLD closes a factor.
+    pub mtld_threshold: f64,
+    /// Number of words drawn in HD-D's hypothetical samples.
+    pub sample_size: usize,
+}
+
+impl Default for DiversityOptions {
+    fn default() -> DiversityOptions {
+        DiversityOptions {
+            case_sensitive: false,
+            window: 50,
+            mtld_threshold: 0.72,
+            sample_size: 42,
+        }
+    }
+}
+
+/// Vocabulary richness of a text.
+///
+/// Words are tokens with any leading and trailing punctuation trimmed, so
+/// "mat." and "mat" are the same type. Stopwords are removed with or without
+/// their punctuation, and tokens without a letter or digit are not words.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Diversity {
+    /// Number of words.
+    pub tokens: u64,
+    /// Number of distinct words.
+    pub types: u64,
+    /// Types that occur exactly once.
+    pub hapax_legomena: u64,
+    /// Types that occur exactly twice.
+    pub dis_legomena: u64,
+    /// Type-token ratio: `types / tokens`.
+    pub ttr: f64,
+    /// Moving-average type-token ratio over every window of
+    /// [`DiversityOptions::window`] consecutive words. A text no longer
+    /// than the window gets its plain type-token ratio.
+    pub mattr: f64,
+    /// Measure of Textual Lexical Diversity: the mean length of the runs of
+    /// words whose type-token ratio stays above the threshold, averaged
+    /// over reading forwards and backwards. `None` if no run ever falls to
+    /// the threshold, as when every word is different.
+    pub mtld: Option<f64>,
+    /// HD-D: the expected type-token ratio of a random sample of
+    /// [`DiversityOptions::sample_size`] words, from the hypergeometric
+    /// distribution. `None` if the text has fewer words than that.
+    pub hdd: Option<f64>,
+}
+
+/// Measures the lexical diversity of `text`, splitting and filtering words
+/// as `config` does, or returns [`Error::EmptyInput`] if it has no words.
+///
+/// This is one pass over the text, building a table of its distinct words.
+pub fn diversity(
+    text: &str,
+    config: &Config,
+    options: &DiversityOptions,
+) -> Result<Diversity, Error> {
+    let vocabulary = Vocabulary::new(text, config, options.case_sensitive);
+    let tokens = vocabulary.sequence.len();
+    if tokens == 0 {
+        return Err(Error::EmptyInput);
+    }
+    let counts = &vocabulary.counts;
+    let with_count = |n| counts.iter().filter(|&&count| count == n).count() as u64;
+    Ok(Diversity {
+        tokens: tokens as u64,
+        types: counts.len() as u64,
+        hapax_legomena: with_count(1),
+        dis_legomena: with_count(2),
+        ttr: counts.len() as f64 / tokens as f64,
+        mattr: mattr(&vocabulary.sequence, counts.len(), options.window),
+        mtld: mtld(&vocabulary.sequence, counts.len(), options.mtld_threshold),
+        hdd: hdd(counts, tokens, options.sample_size),
+    })
+}
+
+/// The distinct words of a text and where they occur.
+#[derive(Debug, Default)]
+pub(crate) struct Vocabulary<'a> {
+    /// Each distinct word, case-folded if asked, with its id.
+    pub(crate) ids: HashMap<Cow<'a, str>, usize>,
+    /// How often each word occurs, by id.
+    pub(crate) counts: Vec<u64>,
+    /// The id of every word of the text, in order.
+    pub(crate) sequence: Vec<usize>,
+}
+
+impl<'a> Vocabulary<'a> {
+    /// Collects the words of `text` as [`Diversity`] defines them.
+    pub(crate) fn new(text: &'a str, config: &Config, case_sensitive: bool) -> Vocabulary<'a> {
+        let mut vocabulary = Vocabulary::default();
+        for token in config.tokenizer.tokens(text) {
+            let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+            // A stopword may carry punctuation, as in "the," or "e.g.".
+            if word.is_empty() || config.is_stopword(word) || config.is_stopword(token) {
+                continue;
+            }
+            // Only allocate for words that actually change.
+            let word = if !case_sensitive && word.chars().any(char::is_uppercase) {
+                Cow::Owned(word.to_lowercase())
+            } else {
+                Cow::Borrowed(word)
+            };
+            let next = vocabulary.ids.len();
+            let id = *vocabulary.ids.entry(word).or_insert(next);
+            if id == next {
+                vocabulary.counts.push(0);
+            }
+            vocabulary.counts[id] += 1;
+            vocabulary.sequence.push(id);
+        }
+        vocabulary
+    }
+}
+
+/// Mean type-token ratio of every `window` consecutive words of
+/// `sequence`, whose ids are below `types`.
+fn mattr(sequence: &[usize], types: usize, window: usize) -> f64 {
+    let window = if window == 0 {
+        sequence.len()
+    } else {
+        window.min(sequence.len())
+    };
+    let mut in_window = vec![0u32; types];
+    let mut distinct = 0;
+    for &id in &sequence[..window] {
+        distinct += (in_window[id] == 0) as usize;
+        in_window[id] += 1;
+    }
+    let mut total = distinct;
+    for (&out, &id) in sequence.iter().zip(&sequence[window..]) {
+        in_window[out] -= 1;
+        distinct -= (in_window[out] == 0) as usize;
+        distinct += (in_window[id] == 0) as usize;
+        in_window[id] += 1;
+        total += distinct;
+    }
+    let windows = sequence.len() - window + 1;
+    total as f64 / (windows * window) as f64
+}
+
+/// MTLD of `sequence`, whose ids are below `types`: its length divided by
+/// the number of factors, averaged over both directions.
+fn mtld(sequence: &[usize], types: usize, threshold: f64) -> Option<f64> {
+    let forward = mtld_factors(sequence.iter().copied(), types, threshold);
+    let backward = mtld_factors(sequence.iter().rev().copied(), types, threshold);
+    if forward == 0.0 || backward == 0.0 {
+        return None;
+    }
+    let tokens = sequence.len() as f64;
+    Some((tokens / forward + tokens / backward) / 2.0)
+}
+
+/// Counts how many times the type-token ratio of the words read so far
+/// falls to `threshold`, starting afresh each time, plus the fraction of the
+/// way the last, unfinished run got there.
+fn mtld_factors(ids: impl Iterator<Item = usize>, types: usize, threshold: f64) -> f64 {
+    // The run in which each type was last seen, so that starting a new run
+    // does not need to clear anything.
+    let mut seen = vec![0; types];
+    let mut run = 1;
+    let (mut factors, mut len, mut distinct) = (0.0, 0, 0);
+    for id in ids {
+        len += 1;
+        if seen[id] != run {
+            seen[id] = run;
+            distinct += 1;
+        }
+        if distinct as f64 / len as f64 <= threshold {
+            factors += 1.0;
+            run += 1;
+            len = 0;
+            distinct = 0;
+        }
+    }
+    if len > 0 {
+        factors += (1.0 - distinct as f64 / len as f64) / (1.0 - threshold);
+    }
+    factors
+}
+
+/// HD-D of a text of `tokens` words whose types occur `counts` times: the
+/// sum over types of the chance that a sample of `sample_size` words
+/// contains it, divided by `sample_size`.
+fn hdd(counts: &[u64], tokens: usize, sample_size: usize) -> Option<f64> {
+    if sample_size == 0 || tokens < sample_size {
+        return None;
+    }
+    // Many types share a count, and the chance depends only on the count.
+    let mut types_with_count: HashMap<u64, u64> = HashMap::new();
+    for &count in counts {
+        *types_with_count.entry(count).or_default() += 1;
+    }
+    let tokens = tokens as f64;
+    let total: f64 = types_with_count
+        .into_iter()
+        .map(|(count, types)| {
+            // The chance that none of the sample is this type.
+            let absent: f64 = (0..sample_size)
+                .map(|i| ((tokens - count as f64 - i as f64) / (tokens - i as f64)).max(0.0))
+                .product();
+            types as f64 * (1.0 - absent)
+        })
+        .sum();
+    Some(total / sample_size as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::corpus;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn counts_types_and_legomena() {
+        let text = "The cat saw the dog. The dog, the CAT... and a bird!";
+        let options = DiversityOptions::default();
+        let d = diversity(text, &Config::default(), &options).unwrap();
+        // "the" is only a stopword in lowercase, and "a" is dropped.
+        assert_eq!((d.tokens, d.types), (9, 6));
+        assert_eq!((d.hapax_legomena, d.dis_legomena), (3, 3));
+        assert!(close(d.ttr, 6.0 / 9.0));
+
+        let options = DiversityOptions {
+            case_sensitive: true,
+            ..options
+        };
+        let d = diversity(text, &Config::default(), &options).unwrap();
+        assert_eq!((d.tokens, d.types), (9, 7));
+    }
+
+    #[test]
+    fn punctuated_stopwords_are_removed() {
+        let text = "(The) cat, the. dog: a; \"a\" bird";
+        let d = diversity(text, &Config::default(), &DiversityOptions::default()).unwrap();
+        // "(The)" is kept, as "The" is not a stopword of the default preset.
+        assert_eq!((d.tokens, d.types), (4, 4));
+
+        let config = Config {
+            stopwords: ["e.g."].iter().map(|s| s.to_string()).collect(),
+            ..Config::default()
+        };
+        let d = diversity("e.g. e.g, cat", &config, &DiversityOptions::default()).unwrap();
+        assert_eq!(d.tokens, 2);
+    }
+
+    #[test]
+    fn moving_average_ttr() {
+        let text = "a b a c";
+        let config = Config {
+            stopwords: Default::default(),
+            ..Config::default()
+        };
+        let options = DiversityOptions {
+            window: 2,
+            ..DiversityOptions::default()
+        };
+        // Windows "a b", "b a" and "a c" all have two types.
+        let d = diversity(text, &config, &options).unwrap();
+        assert!(close(d.mattr, 1.0));
+        let options = DiversityOptions {
+            window: 3,
+            ..options
+        };
+        // "a b a" has two types and "b a c" three.
+        let d = diversity(text, &config, &options).unwrap();
+        assert!(close(d.mattr, 5.0 / 6.0));
+        let options = DiversityOptions {
+            window: 10,
+            ..options
+        };
+        let d = diversity(text, &config, &options).unwrap();
+        assert!(close(d.mattr, d.ttr));
+    }
+
+    #[test]
+    fn mtld_factors_runs() {
+        // TTR 1, 1, 2/3 (a factor), then "b b" ends at TTR 1/2 (a factor).
+        assert!(close(
+            mtld_factors([0, 1, 0, 1, 1].into_iter(), 2, 0.72),
+            2.0
+        ));
+        // An unfinished run at TTR 1 counts for nothing.
+        assert!(close(mtld_factors([0, 1].into_iter(), 2, 0.72), 0.0));
+        // "a a" falls to 1/2 at once; "b" is left at TTR 1.
+        assert!(close(mtld_factors([0, 0, 1].into_iter(), 2, 0.5), 1.0));
+        assert_eq!(mtld(&[0, 1, 2], 3, 0.72), None);
+    }
+
+    #[test]
+    fn hdd_is_expected_sample_ttr() {
+        // With two types of two words each, a sample of two words holds
+        // both with probability 2/3, so its expected TTR is 5/6.
+        assert!(close(hdd(&[2, 2], 4, 2).unwrap(), 5.0 / 6.0));
+        // A sample of the whole text sees every type.
+        assert!(close(hdd(&[3, 1], 4, 4).unwrap(), 2.0 / 4.0));
+        assert_eq!(hdd(&[1], 1, 2), None);
+    }
+
+    #[test]
+    fn empty_text_is_an_error() {
+        let options = DiversityOptions::default();
+        assert_eq!(
+            diversity("the — ...", &Config::default(), &options),
+            Err(Error::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn corpus_results_are_reproducible() {
+        let text = corpus();
+        let d = diversity(&text, &Config::default(), &DiversityOptions::default()).unwrap();
+        assert_eq!((d.tokens, d.types), (166812, 5936));
+        // The corpus is the same extracts six times over.
+        assert_eq!((d.hapax_legomena, d.dis_legomena), (0, 0));
+        assert!(close(d.ttr, 5936.0 / 166812.0));
+        assert!(close(d.mattr, 0.86261113076630));
+        assert!(close(d.mtld.unwrap(), 164.28346518095663));
+        assert!(close(d.hdd.unwrap(), 0.91152738315862));
+
+        let options = DiversityOptions {
+            case_sensitive: true,
+            ..DiversityOptions::default()
+        };
+        let d = diversity(&text, &Config::default(), &options).unwrap();
+        assert_eq!((d.tokens, d.types), (166812, 6393));
+    }
+}
@@ -0,0 +1,36 @@
+//! Why a ratio could not be computed.
+
+use std::fmt;
+use std::str::Utf8Error;
+
+/// Reasons the short/long ratio is undefined for an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// No words were left after stopwords were removed, so the ratio would
+    /// be 0/0.
+    EmptyInput,
+    /// The input is not valid UTF-8. `valid_up_to` is the byte offset of the
+    /// first invalid sequence.
+    InvalidUtf8 { valid_up_to: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyInput => write!(f, "no words left after removing stopwords"),
+            Error::InvalidUtf8 { valid_up_to } => {
+                write!(f, "invalid UTF-8 at byte offset {}", valid_up_to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Error {
+        Error::InvalidUtf8 {
+            valid_up_to: e.valid_up_to(),
+        }
+    }
+}
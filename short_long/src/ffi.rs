@@ -0,0 +1,1754 @@
+//! C-ABI exports for CFFI and other foreign callers.
+//!
+//! Every function here only converts C-like arguments into Rust types and
+//! hands them to the safe API in the crate root. A null text pointer is
+//! accepted as an empty string when its length is 0 and rejected otherwise,
+//! and a panic is caught and reported instead of unwinding into the caller.
+//!
+//! The C declarations of everything here are in `short_long.h`, which is
+//! generated with cbindgen and checked by the `header_is_up_to_date` test.
+//! Run the tests with `UPDATE_HEADER=1` set to regenerate it.
+
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::str;
+
+use crate::{
+    Accumulator, Config, Diversity, DiversityOptions, Error, LengthUnit, Readability, Stats,
+    Tokenizer,
+};
+
+/// Outcome of a checked call, returned instead of a bare NaN.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortLongStatus {
+    /// The ratio was computed.
+    Ok = 0,
+    /// The text is not valid UTF-8; the byte offset of the first invalid
+    /// sequence is reported separately.
+    InvalidUtf8 = 1,
+    /// The stopwords in the [`ShortLongConfig`] are null or not valid UTF-8,
+    /// or its unit or tokenizer is unknown.
+    InvalidConfig = 2,
+    /// No words were left after removing stopwords, so the ratio is
+    /// undefined rather than 0.
+    EmptyInput = 3,
+    /// The text pointer was null but its length was not 0.
+    NullPointer = 4,
+    /// The library panicked. This is a bug in the library, not the input.
+    Panic = 5,
+    /// An argument other than the text is malformed, such as batch offsets
+    /// that decrease or run past the end of the buffer.
+    InvalidArgument = 6,
+    /// An output buffer is too small; the required size is reported
+    /// separately.
+    BufferTooSmall = 7,
+}
+
+impl From<Error> for ShortLongStatus {
+    fn from(e: Error) -> ShortLongStatus {
+        match e {
+            Error::EmptyInput => ShortLongStatus::EmptyInput,
+            Error::InvalidUtf8 { .. } => ShortLongStatus::InvalidUtf8,
+        }
+    }
+}
+
+/// A ratio together with the status explaining whether it is meaningful.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongResult {
+    pub status: ShortLongStatus,
+    /// The ratio if `status` is [`ShortLongStatus::Ok`], NaN otherwise.
+    pub ratio: f64,
+}
+
+impl From<Result<f64, Error>> for ShortLongResult {
+    fn from(result: Result<f64, Error>) -> ShortLongResult {
+        match result {
+            Ok(ratio) => ShortLongResult {
+                status: ShortLongStatus::Ok,
+                ratio,
+            },
+            Err(e) => ShortLongResult::failed(e.into()),
+        }
+    }
+}
+
+impl ShortLongResult {
+    fn failed(status: ShortLongStatus) -> ShortLongResult {
+        ShortLongResult {
+            status,
+            ratio: f64::NAN,
+        }
+    }
+}
+
+/// C-compatible counterpart of [`Stats`], with the ratio and
+/// [`Readability`] indices filled in.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShortLongStats {
+    /// Every word in the text, stopwords included.
+    pub total_tokens: u64,
+    /// Words left after removing stopwords: the ratio's denominator.
+    pub filtered_tokens: u64,
+    /// Filtered words longer than the threshold: the ratio's numerator.
+    pub long_tokens: u64,
+    /// Summed length of the filtered words, in the configured unit.
+    pub total_chars: u64,
+    /// `long_tokens / filtered_tokens`, or NaN if `filtered_tokens` is 0.
+    pub ratio: f64,
+    /// Words containing a letter or digit, stopwords included.
+    pub words: u64,
+    /// Words that end a sentence.
+    pub sentences: u64,
+    /// Estimated syllables in `words`.
+    pub syllables: u64,
+    /// Words of three or more syllables.
+    pub polysyllables: u64,
+    /// Letters and digits in `words`.
+    pub letters: u64,
+    /// Flesch Reading Ease, or NaN if `words` is 0, like every index below.
+    pub flesch_reading_ease: f64,
+    /// Flesch–Kincaid Grade Level.
+    pub flesch_kincaid_grade: f64,
+    /// Gunning Fog index.
+    pub gunning_fog: f64,
+    /// SMOG grade.
+    pub smog: f64,
+    /// Coleman–Liau index.
+    pub coleman_liau: f64,
+    /// Automated Readability Index.
+    pub automated_readability: f64,
+    /// `polysyllables / words`, or NaN if `words` is 0.
+    pub polysyllabic_ratio: f64,
+    /// `words / sentences`, the average sentence length, or NaN if `words`
+    /// is 0. A text without a sentence terminator counts as one sentence.
+    pub words_per_sentence: f64,
+}
+
+impl From<Stats> for ShortLongStats {
+    fn from(stats: Stats) -> ShortLongStats {
+        let readability = stats.readability().unwrap_or(Readability {
+            flesch_reading_ease: f64::NAN,
+            flesch_kincaid_grade: f64::NAN,
+            gunning_fog: f64::NAN,
+            smog: f64::NAN,
+            coleman_liau: f64::NAN,
+            automated_readability: f64::NAN,
+        });
+        ShortLongStats {
+            total_tokens: stats.total_tokens,
+            filtered_tokens: stats.filtered_tokens,
+            long_tokens: stats.long_tokens,
+            total_chars: stats.total_chars,
+            ratio: stats.ratio().unwrap_or(f64::NAN),
+            words: stats.words,
+            sentences: stats.sentences,
+            syllables: stats.syllables,
+            polysyllables: stats.polysyllables,
+            letters: stats.letters,
+            flesch_reading_ease: readability.flesch_reading_ease,
+            flesch_kincaid_grade: readability.flesch_kincaid_grade,
+            gunning_fog: readability.gunning_fog,
+            smog: readability.smog,
+            coleman_liau: readability.coleman_liau,
+            automated_readability: readability.automated_readability,
+            polysyllabic_ratio: stats.polysyllabic_ratio().unwrap_or(f64::NAN),
+            words_per_sentence: stats.words_per_sentence().unwrap_or(f64::NAN),
+        }
+    }
+}
+
+impl From<ShortLongStats> for Stats {
+    fn from(stats: ShortLongStats) -> Stats {
+        Stats {
+            total_tokens: stats.total_tokens,
+            filtered_tokens: stats.filtered_tokens,
+            long_tokens: stats.long_tokens,
+            total_chars: stats.total_chars,
+            words: stats.words,
+            sentences: stats.sentences,
+            syllables: stats.syllables,
+            polysyllables: stats.polysyllables,
+            letters: stats.letters,
+        }
+    }
+}
+
+/// Longest possible encoding written by [`short_long_stats_encode`].
+// A literal, as cbindgen cannot evaluate `Stats::MAX_ENCODED_LEN`.
+pub const SHORT_LONG_STATS_MAX_ENCODED_LEN: usize = 1 + 9 * 10;
+
+const _: () = assert!(SHORT_LONG_STATS_MAX_ENCODED_LEN == Stats::MAX_ENCODED_LEN);
+
+/// One document of a batch: `len` bytes of UTF-8 text at `ptr`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongDocument {
+    /// May be null when `len` is 0.
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+/// C-compatible counterpart of [`Diversity`], with NaN for the measures
+/// that are undefined.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShortLongDiversity {
+    /// Words left after removing stopwords and punctuation.
+    pub tokens: u64,
+    /// Distinct words.
+    pub types: u64,
+    /// Types that occur exactly once.
+    pub hapax_legomena: u64,
+    /// Types that occur exactly twice.
+    pub dis_legomena: u64,
+    /// `types / tokens`.
+    pub ttr: f64,
+    /// Moving-average type-token ratio.
+    pub mattr: f64,
+    /// MTLD, or NaN if no run of words falls to the threshold.
+    pub mtld: f64,
+    /// HD-D, or NaN if the text is shorter than the sample size.
+    pub hdd: f64,
+}
+
+impl From<Diversity> for ShortLongDiversity {
+    fn from(diversity: Diversity) -> ShortLongDiversity {
+        ShortLongDiversity {
+            tokens: diversity.tokens,
+            types: diversity.types,
+            hapax_legomena: diversity.hapax_legomena,
+            dis_legomena: diversity.dis_legomena,
+            ttr: diversity.ttr,
+            mattr: diversity.mattr,
+            mtld: diversity.mtld.unwrap_or(f64::NAN),
+            hdd: diversity.hdd.unwrap_or(f64::NAN),
+        }
+    }
+}
+
+/// C-compatible counterpart of [`DiversityOptions`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongDiversityOptions {
+    /// Whether words that differ only in case count as different types.
+    pub case_sensitive: bool,
+    /// Length in words of the moving window for MATTR.
+    pub window: usize,
+    /// Type-token ratio at which MTLD closes a factor. Must not be NaN.
+    pub mtld_threshold: f64,
+    /// Number of words drawn in HD-D's hypothetical samples.
+    pub sample_size: usize,
+}
+
+impl From<DiversityOptions> for ShortLongDiversityOptions {
+    fn from(options: DiversityOptions) -> ShortLongDiversityOptions {
+        ShortLongDiversityOptions {
+            case_sensitive: options.case_sensitive,
+            window: options.window,
+            mtld_threshold: options.mtld_threshold,
+            sample_size: options.sample_size,
+        }
+    }
+}
+
+impl ShortLongDiversityOptions {
+    /// Converts to [`DiversityOptions`], or `None` if the MTLD threshold is
+    /// NaN.
+    fn to_options(self) -> Option<DiversityOptions> {
+        if self.mtld_threshold.is_nan() {
+            return None;
+        }
+        Some(DiversityOptions {
+            case_sensitive: self.case_sensitive,
+            window: self.window,
+            mtld_threshold: self.mtld_threshold,
+            sample_size: self.sample_size,
+        })
+    }
+}
+
+/// One entry of [`ShortLongTopWords`]: `len` bytes of UTF-8 at `word`,
+/// not NUL-terminated, which occur `count` times.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongWordCount {
+    pub word: *const u8,
+    pub len: usize,
+    pub count: u64,
+}
+
+/// The most frequent words of a text, owned by the library until passed to
+/// [`short_long_top_words_free`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongTopWords {
+    /// `count` entries, most frequent first, or null if `count` is 0.
+    pub words: *mut ShortLongWordCount,
+    pub count: usize,
+}
+
+/// Where one piece of a text lies: the bytes from `start` up to `end`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShortLongSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// C-compatible counterpart of [`Config`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ShortLongConfig {
+    /// Words longer than this many `unit`s count as long.
+    pub threshold: usize,
+    /// What word lengths are measured in, as a [`LengthUnit`] value.
+    pub unit: u32,
+    /// Whitespace-separated UTF-8 list of words to ignore. May be null when
+    /// `stopwords_len` is 0.
+    pub stopwords: *const u8,
+    /// Length of `stopwords` in bytes.
+    pub stopwords_len: usize,
+    /// Whether a word must match a stopword's case exactly to be ignored.
+    pub case_sensitive: bool,
+    /// How the text is split into words, as a [`Tokenizer`] value. This and
+    /// `unit` are plain integers so that an unknown value is an error rather
+    /// than undefined behaviour.
+    pub tokenizer: u32,
+}
+
+impl ShortLongConfig {
+    /// Converts to a [`Config`], or `None` if the stopwords are null or not
+    /// UTF-8 or the unit or tokenizer is unknown.
+    ///
+    /// # Safety
+    ///
+    /// `stopwords` must be null or point to `stopwords_len` readable bytes.
+    unsafe fn to_config(self) -> Option<Config> {
+        let stopwords = str::from_utf8(array(self.stopwords, self.stopwords_len).ok()?).ok()?;
+        Some(Config {
+            threshold: self.threshold,
+            unit: LengthUnit::try_from(self.unit).ok()?,
+            stopwords: stopwords.split_whitespace().map(str::to_string).collect(),
+            case_sensitive: self.case_sensitive,
+            tokenizer: Tokenizer::try_from(self.tokenizer).ok()?,
+        })
+    }
+}
+
+/// Borrows `len` values at `ptr`, accepting a null `ptr` as empty if `len`
+/// is 0.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable values that outlive `'a`.
+unsafe fn array<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], ShortLongStatus> {
+    if !ptr.is_null() {
+        Ok(slice::from_raw_parts(ptr, len))
+    } else if len == 0 {
+        Ok(&[])
+    } else {
+        Err(ShortLongStatus::NullPointer)
+    }
+}
+
+/// Like [`array`], but borrows the values mutably.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for reading and writing `len` values that
+/// outlive `'a`.
+unsafe fn array_mut<'a, T>(ptr: *mut T, len: usize) -> Result<&'a mut [T], ShortLongStatus> {
+    if !ptr.is_null() {
+        Ok(slice::from_raw_parts_mut(ptr, len))
+    } else if len == 0 {
+        Ok(&mut [])
+    } else {
+        Err(ShortLongStatus::NullPointer)
+    }
+}
+
+/// Reads the config at `config`, falling back to the default preset if it is
+/// null.
+///
+/// # Safety
+///
+/// `config` must be null or point to a valid [`ShortLongConfig`].
+unsafe fn read_config(config: *const ShortLongConfig) -> Result<Config, ShortLongStatus> {
+    match config.as_ref() {
+        Some(config) => config.to_config().ok_or(ShortLongStatus::InvalidConfig),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Runs `f`, returning `on_panic` instead if it panics, so that a panic
+/// never unwinds across the C boundary.
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+/// Returns the default preset: Python-style word splitting and a threshold
+/// of 8 characters, ignoring "the" and "a" case-sensitively.
+#[no_mangle]
+pub extern "C" fn short_long_default_config() -> ShortLongConfig {
+    const STOPWORDS: &str = "the a";
+    ShortLongConfig {
+        threshold: 8,
+        unit: LengthUnit::Chars as u32,
+        stopwords: STOPWORDS.as_ptr(),
+        stopwords_len: STOPWORDS.len(),
+        case_sensitive: true,
+        tokenizer: Tokenizer::Python as u32,
+    }
+}
+
+/// Returns the fraction of words longer than 8 characters in the UTF-8
+/// string at `ptr`, ignoring "the" and "a". Returns NaN if there are no such
+/// words, the bytes are not UTF-8, or `ptr` is null with a non-zero `len`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long(ptr: *const u8, len: usize) -> f64 {
+    short_long_result(ptr, len, std::ptr::null()).ratio
+}
+
+/// Returns the fraction of words with three or more syllables in the UTF-8
+/// string at `ptr`, stopwords included. Returns NaN if there are no words,
+/// the bytes are not UTF-8, or `ptr` is null with a non-zero `len`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_polysyllabic(ptr: *const u8, len: usize) -> f64 {
+    guard(f64::NAN, || match array(ptr, len).map(str::from_utf8) {
+        Ok(Ok(text)) => crate::polysyllabic_ratio(text),
+        _ => f64::NAN,
+    })
+}
+
+/// Like [`short_long`], but with the threshold, unit and stopwords taken from
+/// `config`, or the default preset if `config` is null. Also returns NaN if
+/// the stopwords are not UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, and `config` must be
+/// null or point to a valid [`ShortLongConfig`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_with_config(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+) -> f64 {
+    short_long_result(ptr, len, config).ratio
+}
+
+/// Like [`short_long_with_config`], but validates the text and reports
+/// failures through the returned status instead of NaN.
+///
+/// On success the ratio is written to `ratio_out`. If the text is not UTF-8,
+/// [`ShortLongStatus::InvalidUtf8`] is returned and the byte offset of the
+/// first invalid sequence is written to `error_offset_out`, unless `lossy` is
+/// set, in which case invalid sequences are replaced with U+FFFD and the
+/// ratio is computed anyway. [`ShortLongStatus::EmptyInput`] is returned if
+/// no words are left after removing stopwords. Either out-parameter may be
+/// null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], and each out-parameter must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_checked(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    lossy: bool,
+    ratio_out: *mut f64,
+    error_offset_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let bytes = match array(ptr, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        let result = if lossy {
+            crate::ratio_bytes_lossy(bytes, &config)
+        } else {
+            crate::ratio_bytes(bytes, &config)
+        };
+        match result {
+            Ok(ratio) => {
+                if let Some(out) = ratio_out.as_mut() {
+                    *out = ratio;
+                }
+                ShortLongStatus::Ok
+            }
+            Err(e) => {
+                if let (Error::InvalidUtf8 { valid_up_to }, Some(out)) =
+                    (e, error_offset_out.as_mut())
+                {
+                    *out = valid_up_to;
+                }
+                e.into()
+            }
+        }
+    })
+}
+
+/// Like [`short_long_with_config`], but returns the ratio together with a
+/// status, so that "no words" ([`ShortLongStatus::EmptyInput`]) can be told
+/// apart from "no long words" (a ratio of 0).
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, and `config` must be
+/// null or point to a valid [`ShortLongConfig`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_result(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+) -> ShortLongResult {
+    guard(ShortLongResult::failed(ShortLongStatus::Panic), || {
+        let bytes = match array(ptr, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return ShortLongResult::failed(status),
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return ShortLongResult::failed(status),
+        };
+        crate::ratio_bytes(bytes, &config).into()
+    })
+}
+
+/// Counts the words of the UTF-8 string at `ptr` and writes every count,
+/// plus the ratio, to `stats_out`.
+///
+/// Text without words is not an error here: the counts are still written,
+/// with a NaN ratio, and [`ShortLongStatus::Ok`] is returned. If the text is
+/// not UTF-8, [`ShortLongStatus::InvalidUtf8`] is returned and `stats_out`
+/// is left untouched.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], and `stats_out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_stats(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    stats_out: *mut ShortLongStats,
+) -> ShortLongStatus {
+    short_long_stats_parallel(ptr, len, config, 1, stats_out)
+}
+
+/// Like [`short_long_stats`], but counts large texts on up to `threads`
+/// threads, or one per CPU if `threads` is 0. The result is identical to the
+/// serial one.
+///
+/// # Safety
+///
+/// Same as [`short_long_stats`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_stats_parallel(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    threads: usize,
+    stats_out: *mut ShortLongStats,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let bytes = match array(ptr, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        match str::from_utf8(bytes) {
+            Ok(text) => {
+                let stats = crate::stats_parallel(text, &config, threads);
+                if let Some(out) = stats_out.as_mut() {
+                    *out = stats.into();
+                }
+                ShortLongStatus::Ok
+            }
+            Err(e) => Error::from(e).into(),
+        }
+    })
+}
+
+/// Counts the words of the UTF-8 string at `ptr` by length, as
+/// [`histogram`](crate::histogram) does, into the `buckets` entries at
+/// `counts_out`. The last entry is the overflow bucket for every word at
+/// least `buckets - 1` long. `counts_out` is overwritten, not added to.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], and `counts_out` must be
+/// null or valid for writing `buckets` values.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_histogram(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    counts_out: *mut u64,
+    buckets: usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let bytes = match array(ptr, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        if counts_out.is_null() && buckets > 0 {
+            return ShortLongStatus::NullPointer;
+        }
+        let text = match str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => return Error::from(e).into(),
+        };
+        let counts = crate::histogram(text, &config, buckets);
+        if buckets > 0 {
+            slice::from_raw_parts_mut(counts_out, buckets).copy_from_slice(&counts);
+        }
+        ShortLongStatus::Ok
+    })
+}
+
+/// Returns the default [`DiversityOptions`]: case folded, a 50-word MATTR
+/// window, an MTLD threshold of 0.72 and 42-word HD-D samples.
+#[no_mangle]
+pub extern "C" fn short_long_default_diversity_options() -> ShortLongDiversityOptions {
+    DiversityOptions::default().into()
+}
+
+/// Measures the lexical diversity of the UTF-8 string at `ptr`, as
+/// [`diversity`](crate::diversity) does, and writes it to `diversity_out`.
+/// A null `config` or `options` selects the default. Returns
+/// [`ShortLongStatus::EmptyInput`] without writing anything if the text has
+/// no words, or [`ShortLongStatus::InvalidArgument`] if the MTLD threshold
+/// is NaN.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], `options` must be null or
+/// point to valid [`ShortLongDiversityOptions`], and `diversity_out` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_diversity(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    options: *const ShortLongDiversityOptions,
+    diversity_out: *mut ShortLongDiversity,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let text = match array(ptr, len).map(str::from_utf8) {
+            Ok(Ok(text)) => text,
+            Ok(Err(e)) => return Error::from(e).into(),
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        let options = match options.as_ref() {
+            Some(options) => match options.to_options() {
+                Some(options) => options,
+                None => return ShortLongStatus::InvalidArgument,
+            },
+            None => DiversityOptions::default(),
+        };
+        match crate::diversity(text, &config, &options) {
+            Ok(diversity) => {
+                if let Some(out) = diversity_out.as_mut() {
+                    *out = diversity.into();
+                }
+                ShortLongStatus::Ok
+            }
+            Err(e) => e.into(),
+        }
+    })
+}
+
+/// Writes `values` to `out`, which has room for `capacity` of them, and
+/// their number to `count_out`. Writes nothing to `out` and returns
+/// [`ShortLongStatus::BufferTooSmall`] if they do not fit.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writing `capacity` values, and
+/// `count_out` must be null or valid for writes.
+unsafe fn write_all<T: Copy>(
+    values: &[T],
+    out: *mut T,
+    capacity: usize,
+    count_out: *mut usize,
+) -> ShortLongStatus {
+    let out = match array_mut(out, capacity) {
+        Ok(out) => out,
+        Err(status) => return status,
+    };
+    if let Some(count_out) = count_out.as_mut() {
+        *count_out = values.len();
+    }
+    match out.get_mut(..values.len()) {
+        Some(out) => {
+            out.copy_from_slice(values);
+            ShortLongStatus::Ok
+        }
+        None => ShortLongStatus::BufferTooSmall,
+    }
+}
+
+/// Splits the UTF-8 string at `ptr` into sentences, as
+/// [`sentences`](crate::sentences) does, writing the byte span of each to
+/// `spans_out` and their number to `count_out`. If `capacity` is too small,
+/// nothing is written to `spans_out`, the number of sentences is still
+/// written to `count_out`, and [`ShortLongStatus::BufferTooSmall`] is
+/// returned.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `spans_out` must be
+/// null or valid for writing `capacity` spans, and `count_out` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_sentences(
+    ptr: *const u8,
+    len: usize,
+    spans_out: *mut ShortLongSpan,
+    capacity: usize,
+    count_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let text = match array(ptr, len).map(str::from_utf8) {
+            Ok(Ok(text)) => text,
+            Ok(Err(e)) => return Error::from(e).into(),
+            Err(status) => return status,
+        };
+        // Sentences are subslices of `text`, so their offsets follow from
+        // their addresses.
+        let spans: Vec<ShortLongSpan> = crate::sentences(text)
+            .map(|sentence| {
+                let start = sentence.as_ptr() as usize - text.as_ptr() as usize;
+                ShortLongSpan {
+                    start,
+                    end: start + sentence.len(),
+                }
+            })
+            .collect();
+        write_all(&spans, spans_out, capacity, count_out)
+    })
+}
+
+/// Counts each sentence of the UTF-8 string at `ptr`, as
+/// [`sentence_stats`](crate::sentence_stats) does, writing one
+/// [`ShortLongStats`] per sentence to `stats_out` and their number to
+/// `count_out`. A sentence with no words left after removing stopwords has
+/// a NaN ratio. If `capacity` is too small, nothing is written to
+/// `stats_out`, the number of sentences is still written to `count_out`,
+/// and [`ShortLongStatus::BufferTooSmall`] is returned.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], `stats_out` must be null or
+/// valid for writing `capacity` stats, and `count_out` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_sentence_stats(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    stats_out: *mut ShortLongStats,
+    capacity: usize,
+    count_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let text = match array(ptr, len).map(str::from_utf8) {
+            Ok(Ok(text)) => text,
+            Ok(Err(e)) => return Error::from(e).into(),
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        let stats: Vec<ShortLongStats> = crate::sentence_stats(text, &config)
+            .into_iter()
+            .map(ShortLongStats::from)
+            .collect();
+        write_all(&stats, stats_out, capacity, count_out)
+    })
+}
+
+/// Writes the ratios of `docs` into `results_out`, one [`ShortLongResult`]
+/// per document.
+fn write_batch(
+    docs: &[&[u8]],
+    config: &Config,
+    threads: usize,
+    results_out: &mut [ShortLongResult],
+) {
+    let results = crate::ratio_batch_parallel(docs, config, threads);
+    for (out, result) in results_out.iter_mut().zip(results) {
+        *out = result.into();
+    }
+}
+
+/// Scores `count` documents in one call, writing the ratio and status of
+/// `docs[i]` to `results_out[i]` as [`short_long_result`] would.
+///
+/// The returned status covers the call as a whole: [`ShortLongStatus::Ok`]
+/// means every result was written, even if some documents failed on their
+/// own.
+///
+/// # Safety
+///
+/// `docs` must be null or point to `count` documents, each of which is
+/// either null with length 0 or points to `len` readable bytes. `config`
+/// must be null or point to a valid [`ShortLongConfig`], and `results_out`
+/// must be null or valid for writing `count` results.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_batch(
+    docs: *const ShortLongDocument,
+    count: usize,
+    config: *const ShortLongConfig,
+    results_out: *mut ShortLongResult,
+) -> ShortLongStatus {
+    short_long_batch_parallel(docs, count, config, 1, results_out)
+}
+
+/// Like [`short_long_batch`], but spreads the documents over up to
+/// `threads` threads, or one per CPU if `threads` is 0.
+///
+/// # Safety
+///
+/// Same as [`short_long_batch`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_batch_parallel(
+    docs: *const ShortLongDocument,
+    count: usize,
+    config: *const ShortLongConfig,
+    threads: usize,
+    results_out: *mut ShortLongResult,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let docs = match array(docs, count) {
+            Ok(docs) => docs,
+            Err(status) => return status,
+        };
+        let results_out = match array_mut(results_out, count) {
+            Ok(results_out) => results_out,
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        let docs: Result<Vec<&[u8]>, _> = docs.iter().map(|doc| array(doc.ptr, doc.len)).collect();
+        match docs {
+            Ok(docs) => {
+                write_batch(&docs, &config, threads, results_out);
+                ShortLongStatus::Ok
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Like [`short_long_batch`], but with the `count` documents concatenated
+/// in one buffer: document `i` is the bytes from `offsets[i]` up to
+/// `offsets[i + 1]`, so `offsets` has `count + 1` entries. Returns
+/// [`ShortLongStatus::InvalidArgument`] without writing any results if the
+/// offsets decrease or run past `len`, or if `count + 1` overflows.
+///
+/// # Safety
+///
+/// `buf` must be null or point to `len` readable bytes, `offsets` must point
+/// to `count + 1` values, `config` must be null or point to a valid
+/// [`ShortLongConfig`], and `results_out` must be null or valid for writing
+/// `count` results.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_batch_offsets(
+    buf: *const u8,
+    len: usize,
+    offsets: *const usize,
+    count: usize,
+    config: *const ShortLongConfig,
+    results_out: *mut ShortLongResult,
+) -> ShortLongStatus {
+    short_long_batch_offsets_parallel(buf, len, offsets, count, config, 1, results_out)
+}
+
+/// Like [`short_long_batch_offsets`], but spreads the documents over up to
+/// `threads` threads, or one per CPU if `threads` is 0.
+///
+/// # Safety
+///
+/// Same as [`short_long_batch_offsets`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_batch_offsets_parallel(
+    buf: *const u8,
+    len: usize,
+    offsets: *const usize,
+    count: usize,
+    config: *const ShortLongConfig,
+    threads: usize,
+    results_out: *mut ShortLongResult,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let buf = match array(buf, len) {
+            Ok(buf) => buf,
+            Err(status) => return status,
+        };
+        let offsets = match count.checked_add(1).map(|n| array(offsets, n)) {
+            Some(Ok(offsets)) => offsets,
+            Some(Err(status)) => return status,
+            None => return ShortLongStatus::InvalidArgument,
+        };
+        let results_out = match array_mut(results_out, count) {
+            Ok(results_out) => results_out,
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        match crate::split_offsets(buf, offsets) {
+            Some(docs) => {
+                write_batch(&docs, &config, threads, results_out);
+                ShortLongStatus::Ok
+            }
+            None => ShortLongStatus::InvalidArgument,
+        }
+    })
+}
+
+/// Adds the counts of `a` and `b` and writes the sum, with its ratio
+/// recomputed, to `out`, which may be the same as `a` or `b`. The ratios of
+/// `a` and `b` are ignored. Returns [`ShortLongStatus::InvalidArgument`]
+/// without writing anything if a count overflows.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or point to valid [`ShortLongStats`], and `out`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_stats_merge(
+    a: *const ShortLongStats,
+    b: *const ShortLongStats,
+    out: *mut ShortLongStats,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        // Copy the inputs before borrowing `out`, which may alias them.
+        let sum = match (a.as_ref().copied(), b.as_ref().copied()) {
+            (Some(a), Some(b)) => Stats::from(a).checked_add(&Stats::from(b)),
+            _ => return ShortLongStatus::NullPointer,
+        };
+        let sum = match sum {
+            Some(sum) => sum,
+            None => return ShortLongStatus::InvalidArgument,
+        };
+        match out.as_mut() {
+            Some(out) => {
+                *out = sum.into();
+                ShortLongStatus::Ok
+            }
+            None => ShortLongStatus::NullPointer,
+        }
+    })
+}
+
+/// Serializes the counts of `stats` compactly into `buf_out` for sending to
+/// another process, as [`Stats::encode`] does, and writes the number of
+/// bytes used to `len_out`. If `capacity` is too small, nothing is written
+/// to `buf_out`, the required size is written to `len_out`, and
+/// [`ShortLongStatus::BufferTooSmall`] is returned. A buffer of
+/// `SHORT_LONG_STATS_MAX_ENCODED_LEN` bytes is always large enough.
+///
+/// # Safety
+///
+/// `stats` must be null or point to valid [`ShortLongStats`], `buf_out`
+/// must be null or valid for writing `capacity` bytes, and `len_out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_stats_encode(
+    stats: *const ShortLongStats,
+    buf_out: *mut u8,
+    capacity: usize,
+    len_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let stats = match stats.as_ref() {
+            Some(stats) => Stats::from(*stats),
+            None => return ShortLongStatus::NullPointer,
+        };
+        write_all(&stats.encode(), buf_out, capacity, len_out)
+    })
+}
+
+/// Parses `len` bytes written by [`short_long_stats_encode`] into
+/// `stats_out`, with the ratio recomputed. Returns
+/// [`ShortLongStatus::InvalidArgument`] if the bytes are not a valid
+/// encoding.
+///
+/// # Safety
+///
+/// `buf` must be null or point to `len` readable bytes, and `stats_out`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_stats_decode(
+    buf: *const u8,
+    len: usize,
+    stats_out: *mut ShortLongStats,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let buf = match array(buf, len) {
+            Ok(buf) => buf,
+            Err(status) => return status,
+        };
+        match Stats::decode(buf) {
+            Some(stats) => {
+                if let Some(out) = stats_out.as_mut() {
+                    *out = stats.into();
+                }
+                ShortLongStatus::Ok
+            }
+            None => ShortLongStatus::InvalidArgument,
+        }
+    })
+}
+
+/// Opaque handle counting text fed in chunks; see [`Accumulator`].
+pub struct ShortLongState(Accumulator);
+
+/// Starts counting a text that will be fed in chunks with
+/// [`short_long_state_feed`]. Returns null if the stopwords in `config` are
+/// not UTF-8. The handle must be released with [`short_long_state_free`].
+///
+/// # Safety
+///
+/// `config` must be null or point to a valid [`ShortLongConfig`].
+#[no_mangle]
+pub unsafe extern "C" fn short_long_state_new(
+    config: *const ShortLongConfig,
+) -> *mut ShortLongState {
+    guard(ptr::null_mut(), || match read_config(config) {
+        Ok(config) => Box::into_raw(Box::new(ShortLongState(Accumulator::new(config)))),
+        Err(_) => ptr::null_mut(),
+    })
+}
+
+/// Counts the next `len` bytes of the text. A word or UTF-8 character may
+/// continue in the next chunk.
+///
+/// If the text is not UTF-8, [`ShortLongStatus::InvalidUtf8`] is returned,
+/// the offset of the first invalid sequence from the start of the whole
+/// text is written to `error_offset_out`, and the handle should only be
+/// freed.
+///
+/// # Safety
+///
+/// `state` must be null or a live handle from [`short_long_state_new`],
+/// `ptr` must be null or point to `len` readable bytes, and
+/// `error_offset_out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_state_feed(
+    state: *mut ShortLongState,
+    ptr: *const u8,
+    len: usize,
+    error_offset_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let state = match state.as_mut() {
+            Some(state) => state,
+            None => return ShortLongStatus::NullPointer,
+        };
+        let bytes = match array(ptr, len) {
+            Ok(bytes) => bytes,
+            Err(status) => return status,
+        };
+        match state.0.feed(bytes) {
+            Ok(()) => ShortLongStatus::Ok,
+            Err(e) => {
+                if let (Error::InvalidUtf8 { valid_up_to }, Some(out)) =
+                    (e, error_offset_out.as_mut())
+                {
+                    *out = valid_up_to;
+                }
+                e.into()
+            }
+        }
+    })
+}
+
+/// Counts the words held back at the end of the text and writes the totals
+/// for everything fed to `stats_out`, like [`short_long_stats`] on the whole
+/// text. The handle is then reset, ready to count a new text with the same
+/// config.
+///
+/// # Safety
+///
+/// `state` must be null or a live handle from [`short_long_state_new`], and
+/// `stats_out` and `error_offset_out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_state_finish(
+    state: *mut ShortLongState,
+    stats_out: *mut ShortLongStats,
+    error_offset_out: *mut usize,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let state = match state.as_mut() {
+            Some(state) => state,
+            None => return ShortLongStatus::NullPointer,
+        };
+        let fresh = Accumulator::new(state.0.config().clone());
+        match mem::replace(&mut state.0, fresh).finish() {
+            Ok(stats) => {
+                if let Some(out) = stats_out.as_mut() {
+                    *out = stats.into();
+                }
+                ShortLongStatus::Ok
+            }
+            Err(e) => {
+                if let (Error::InvalidUtf8 { valid_up_to }, Some(out)) =
+                    (e, error_offset_out.as_mut())
+                {
+                    *out = valid_up_to;
+                }
+                e.into()
+            }
+        }
+    })
+}
+
+/// Releases a handle from [`short_long_state_new`]. Null is ignored.
+///
+/// # Safety
+///
+/// `state` must be null or a live handle from [`short_long_state_new`],
+/// which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_state_free(state: *mut ShortLongState) {
+    guard((), || {
+        if !state.is_null() {
+            drop(Box::from_raw(state));
+        }
+    })
+}
+
+/// Finds the `k` most frequent words of the UTF-8 string at `ptr`, as
+/// [`top_words`](crate::top_words) does, and writes them to `result_out`.
+/// On success the words must be released with
+/// [`short_long_top_words_free`]; on failure nothing is written.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` readable bytes, `config` must be
+/// null or point to a valid [`ShortLongConfig`], and `result_out` must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_top_words(
+    ptr: *const u8,
+    len: usize,
+    config: *const ShortLongConfig,
+    k: usize,
+    result_out: *mut ShortLongTopWords,
+) -> ShortLongStatus {
+    guard(ShortLongStatus::Panic, || {
+        let text = match array(ptr, len).map(str::from_utf8) {
+            Ok(Ok(text)) => text,
+            Ok(Err(e)) => return Error::from(e).into(),
+            Err(status) => return status,
+        };
+        let config = match read_config(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        };
+        let result_out = match result_out.as_mut() {
+            Some(result_out) => result_out,
+            None => return ShortLongStatus::NullPointer,
+        };
+        let words: Box<[ShortLongWordCount]> = crate::top_words(text, &config, k)
+            .map(|(word, count)| {
+                let word = Box::into_raw(word.into_owned().into_bytes().into_boxed_slice());
+                ShortLongWordCount {
+                    word: word as *const u8,
+                    len: word.len(),
+                    count,
+                }
+            })
+            .collect();
+        *result_out = ShortLongTopWords {
+            count: words.len(),
+            words: if words.is_empty() {
+                ptr::null_mut()
+            } else {
+                Box::into_raw(words) as *mut ShortLongWordCount
+            },
+        };
+        ShortLongStatus::Ok
+    })
+}
+
+/// Releases the words of a [`ShortLongTopWords`] filled by
+/// [`short_long_top_words`] and resets it to empty, so freeing it twice is
+/// harmless. Null is ignored.
+///
+/// # Safety
+///
+/// `result` must be null or point to a [`ShortLongTopWords`] that is empty
+/// or was filled by [`short_long_top_words`] and not changed since.
+#[no_mangle]
+pub unsafe extern "C" fn short_long_top_words_free(result: *mut ShortLongTopWords) {
+    guard((), || {
+        let result = match result.as_mut() {
+            Some(result) => result,
+            None => return,
+        };
+        if !result.words.is_null() {
+            let words = Box::from_raw(ptr::slice_from_raw_parts_mut(result.words, result.count));
+            for word in words.iter() {
+                drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                    word.word as *mut u8,
+                    word.len,
+                )));
+            }
+        }
+        *result = ShortLongTopWords {
+            words: ptr::null_mut(),
+            count: 0,
+        };
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn header_is_up_to_date() {
+        let crate_dir = env!("CARGO_MANIFEST_DIR");
+        let mut generated = Vec::new();
+        cbindgen::generate(crate_dir)
+            .expect("failed to generate bindings")
+            .write(&mut generated);
+        let path = Path::new(crate_dir).join("short_long.h");
+        let current = fs::read(&path).unwrap_or_default();
+        if current == generated {
+            return;
+        }
+        if env::var_os("UPDATE_HEADER").is_some() {
+            fs::write(&path, &generated).unwrap();
+            return;
+        }
+        panic!(
+            "short_long.h is out of date; rerun with UPDATE_HEADER=1 to regenerate it\n{}",
+            line_diff(
+                &String::from_utf8_lossy(&current),
+                &String::from_utf8_lossy(&generated)
+            )
+        );
+    }
+
+    /// The lines of `old` and `new` between their common first and last
+    /// lines, marked `-` and `+`.
+    fn line_diff(old: &str, new: &str) -> String {
+        let old: Vec<_> = old.lines().collect();
+        let new: Vec<_> = new.lines().collect();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut diff = format!("@@ line {} @@\n", prefix + 1);
+        for line in &old[prefix..old.len() - suffix] {
+            diff += &format!("-{}\n", line);
+        }
+        for line in &new[prefix..new.len() - suffix] {
+            diff += &format!("+{}\n", line);
+        }
+        diff
+    }
+
+    #[test]
+    fn export_matches_safe_api() {
+        let text = "Despite the lack of creative direction";
+        let via_ffi = unsafe { short_long(text.as_ptr(), text.len()) };
+        assert_eq!(via_ffi, crate::ratio(text));
+    }
+
+    #[test]
+    fn polysyllabic_counts_words_of_three_syllables() {
+        let text = "The cat sat on a magnificent mat";
+        let via_ffi = unsafe { short_long_polysyllabic(text.as_ptr(), text.len()) };
+        assert_eq!(via_ffi, 1.0 / 7.0);
+        assert!(unsafe { short_long_polysyllabic(b"\xff".as_ptr(), 1) }.is_nan());
+        assert!(unsafe { short_long_polysyllabic(ptr::null(), 0) }.is_nan());
+    }
+
+    #[test]
+    fn checked_reports_invalid_utf8_offset() {
+        // "café" in Latin-1, as often found in truncated extracts.
+        let text = b"caf\xe9 enormously";
+        let (mut ratio, mut offset) = (-1.0, 0);
+        let status = unsafe {
+            short_long_checked(
+                text.as_ptr(),
+                text.len(),
+                ptr::null(),
+                false,
+                &mut ratio,
+                &mut offset,
+            )
+        };
+        assert_eq!(status, ShortLongStatus::InvalidUtf8);
+        assert_eq!((ratio, offset), (-1.0, 3));
+
+        let status = unsafe {
+            short_long_checked(
+                text.as_ptr(),
+                text.len(),
+                ptr::null(),
+                true,
+                &mut ratio,
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(ratio, 0.5);
+    }
+
+    #[test]
+    fn result_distinguishes_no_words_from_no_long_words() {
+        let result = unsafe { short_long_result(b"a".as_ptr(), 1, ptr::null()) };
+        assert_eq!(result.status, ShortLongStatus::EmptyInput);
+        assert!(result.ratio.is_nan());
+
+        let result = unsafe { short_long_result(b"cat".as_ptr(), 3, ptr::null()) };
+        assert_eq!(result.status, ShortLongStatus::Ok);
+        assert_eq!(result.ratio, 0.0);
+    }
+
+    #[test]
+    fn config_is_applied() {
+        let text = "The tiny sixsix enormously";
+        let mut config = short_long_default_config();
+        config.threshold = 5;
+        config.case_sensitive = false;
+        let via_ffi = unsafe { short_long_with_config(text.as_ptr(), text.len(), &config) };
+        assert_eq!(via_ffi, 2.0 / 3.0);
+    }
+
+    #[test]
+    fn stats_fills_every_field() {
+        let text = "The cat sat on a magnificent mat";
+        let mut stats = ShortLongStats::default();
+        let status =
+            unsafe { short_long_stats(text.as_ptr(), text.len(), ptr::null(), &mut stats) };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(
+            (
+                stats.total_tokens,
+                stats.filtered_tokens,
+                stats.long_tokens,
+                stats.total_chars
+            ),
+            (7, 6, 1, 25)
+        );
+        assert_eq!(stats.ratio, 1.0 / 6.0);
+
+        let status = unsafe { short_long_stats(b"the".as_ptr(), 3, ptr::null(), &mut stats) };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!((stats.total_tokens, stats.filtered_tokens), (1, 0));
+        assert!(stats.ratio.is_nan());
+    }
+
+    #[test]
+    fn histogram_fills_caller_array() {
+        let text = "I am the walrus, indubitably";
+        let mut counts = [u64::MAX; 8];
+        let status = unsafe {
+            short_long_histogram(
+                text.as_ptr(),
+                text.len(),
+                ptr::null(),
+                counts.as_mut_ptr(),
+                8,
+            )
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(counts, [0, 1, 1, 0, 0, 0, 0, 2]);
+
+        let status = unsafe {
+            short_long_histogram(text.as_ptr(), text.len(), ptr::null(), ptr::null_mut(), 8)
+        };
+        assert_eq!(status, ShortLongStatus::NullPointer);
+    }
+
+    #[test]
+    fn diversity_fills_caller_struct() {
+        let text = "The cat saw the dog. The dog, the CAT";
+        let mut out = ShortLongDiversity::default();
+        let status = unsafe {
+            short_long_diversity(
+                text.as_ptr(),
+                text.len(),
+                ptr::null(),
+                ptr::null(),
+                &mut out,
+            )
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!((out.tokens, out.types, out.hapax_legomena), (7, 4, 1));
+        assert!(out.hdd.is_nan());
+
+        let mut options = ShortLongDiversityOptions {
+            case_sensitive: true,
+            ..short_long_default_diversity_options()
+        };
+        let status = unsafe {
+            short_long_diversity(text.as_ptr(), text.len(), ptr::null(), &options, &mut out)
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(out.types, 5);
+
+        options.mtld_threshold = f64::NAN;
+        let status = unsafe {
+            short_long_diversity(text.as_ptr(), text.len(), ptr::null(), &options, &mut out)
+        };
+        assert_eq!(status, ShortLongStatus::InvalidArgument);
+
+        let status =
+            unsafe { short_long_diversity(b"the".as_ptr(), 3, ptr::null(), ptr::null(), &mut out) };
+        assert_eq!(status, ShortLongStatus::EmptyInput);
+    }
+
+    #[test]
+    fn top_words_are_owned_until_freed() {
+        let text = "Dog cat dog bird cat dog";
+        let mut result = ShortLongTopWords {
+            words: ptr::null_mut(),
+            count: 0,
+        };
+        let status =
+            unsafe { short_long_top_words(text.as_ptr(), text.len(), ptr::null(), 2, &mut result) };
+        assert_eq!(status, ShortLongStatus::Ok);
+        let words: Vec<_> = unsafe { slice::from_raw_parts(result.words, result.count) }
+            .iter()
+            .map(|w| {
+                let word = unsafe { slice::from_raw_parts(w.word, w.len) };
+                (str::from_utf8(word).unwrap(), w.count)
+            })
+            .collect();
+        assert_eq!(words, [("cat", 2), ("dog", 2)]);
+        unsafe {
+            short_long_top_words_free(&mut result);
+            assert!(result.words.is_null());
+            short_long_top_words_free(&mut result);
+            short_long_top_words_free(ptr::null_mut());
+        }
+
+        let status =
+            unsafe { short_long_top_words(b"the".as_ptr(), 3, ptr::null(), 5, &mut result) };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!((result.words, result.count), (ptr::null_mut(), 0));
+        let status =
+            unsafe { short_long_top_words(b"the".as_ptr(), 3, ptr::null(), 5, ptr::null_mut()) };
+        assert_eq!(status, ShortLongStatus::NullPointer);
+    }
+
+    #[test]
+    fn sentences_fill_caller_spans() {
+        let text = "Dr. Who?  It is 3.14 km. Fine";
+        let mut spans = [ShortLongSpan::default(); 3];
+        let mut count = 0;
+        let status = unsafe {
+            short_long_sentences(text.as_ptr(), text.len(), spans.as_mut_ptr(), 3, &mut count)
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(count, 3);
+        let sentences = spans.map(|span| &text[span.start..span.end]);
+        assert_eq!(sentences, ["Dr. Who?", "It is 3.14 km.", "Fine"]);
+
+        let mut spans = [ShortLongSpan::default(); 2];
+        let status = unsafe {
+            short_long_sentences(text.as_ptr(), text.len(), spans.as_mut_ptr(), 2, &mut count)
+        };
+        assert_eq!(status, ShortLongStatus::BufferTooSmall);
+        assert_eq!(count, 3);
+        assert_eq!(spans, [ShortLongSpan::default(); 2]);
+    }
+
+    #[test]
+    fn sentence_stats_count_each_sentence() {
+        let text = "The cat sat. The magnificent mat!  The";
+        let mut stats = [ShortLongStats::default(); 3];
+        let mut count = 0;
+        let status = unsafe {
+            short_long_sentence_stats(
+                text.as_ptr(),
+                text.len(),
+                ptr::null(),
+                stats.as_mut_ptr(),
+                3,
+                &mut count,
+            )
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(count, 3);
+        assert_eq!(stats.map(|s| s.total_tokens), [3, 3, 1]);
+        assert_eq!(stats[1].ratio, 1.0 / 3.0);
+        assert_eq!(stats[2].ratio, 0.0);
+
+        let status = unsafe {
+            short_long_sentence_stats(
+                b"\xff".as_ptr(),
+                1,
+                ptr::null(),
+                ptr::null_mut(),
+                0,
+                &mut count,
+            )
+        };
+        assert_eq!(status, ShortLongStatus::InvalidUtf8);
+    }
+
+    #[test]
+    fn batch_writes_one_result_per_document() {
+        let texts: [&[u8]; 3] = [b"tiny enormously", b"the", b"caf\xe9"];
+        let docs = texts.map(|t| ShortLongDocument {
+            ptr: t.as_ptr(),
+            len: t.len(),
+        });
+        let mut results = [ShortLongResult::failed(ShortLongStatus::Panic); 3];
+        let status =
+            unsafe { short_long_batch(docs.as_ptr(), 3, ptr::null(), results.as_mut_ptr()) };
+        assert_eq!(status, ShortLongStatus::Ok);
+        let statuses = results.map(|r| r.status);
+        assert_eq!(
+            statuses,
+            [
+                ShortLongStatus::Ok,
+                ShortLongStatus::EmptyInput,
+                ShortLongStatus::InvalidUtf8
+            ]
+        );
+        assert_eq!(results[0].ratio, 0.5);
+
+        let status = unsafe { short_long_batch(docs.as_ptr(), 3, ptr::null(), ptr::null_mut()) };
+        assert_eq!(status, ShortLongStatus::NullPointer);
+    }
+
+    #[test]
+    fn batch_offsets_splits_one_buffer() {
+        let buf = b"tiny enormouslythe";
+        let mut results = [ShortLongResult::failed(ShortLongStatus::Panic); 2];
+        let status = unsafe {
+            short_long_batch_offsets(
+                buf.as_ptr(),
+                buf.len(),
+                [0, 15, 18].as_ptr(),
+                2,
+                ptr::null(),
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, ShortLongStatus::Ok);
+        assert_eq!(results[0].ratio, 0.5);
+        assert_eq!(results[1].status, ShortLongStatus::EmptyInput);
+
+        let status = unsafe {
+            short_long_batch_offsets(
+                buf.as_ptr(),
+                buf.len(),
+                [0, 19, 18].as_ptr(),
+                2,
+                ptr::null(),
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, ShortLongStatus::InvalidArgument);
+
+        let status = unsafe {
+            short_long_batch_offsets(
+                buf.as_ptr(),
+                buf.len(),
+                [0].as_ptr(),
+                usize::MAX,
+                ptr::null(),
+                results.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, ShortLongStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn state_counts_chunked_text() {
+        let text = "Les élèves étudient";
+        let whole = crate::stats(text, &Config::default());
+        unsafe {
+            let state = short_long_state_new(ptr::null());
+            assert!(!state.is_null());
+            // Split inside the two bytes of the second "è".
+            for chunk in [&text.as_bytes()[..8], &text.as_bytes()[8..]] {
+                let status =
+                    short_long_state_feed(state, chunk.as_ptr(), chunk.len(), ptr::null_mut());
+                assert_eq!(status, ShortLongStatus::Ok);
+            }
+            let mut stats = ShortLongStats::default();
+            let status = short_long_state_finish(state, &mut stats, ptr::null_mut());
+            assert_eq!(status, ShortLongStatus::Ok);
+            assert_eq!(
+                (stats.total_tokens, stats.long_tokens, stats.total_chars),
+                (whole.total_tokens, whole.long_tokens, whole.total_chars)
+            );
+
+            let mut offset = 0;
+            let status = short_long_state_feed(state, b"ok \xff".as_ptr(), 4, &mut offset);
+            assert_eq!((status, offset), (ShortLongStatus::InvalidUtf8, 3));
+            short_long_state_free(state);
+
+            let status = short_long_state_feed(ptr::null_mut(), ptr::null(), 0, ptr::null_mut());
+            assert_eq!(status, ShortLongStatus::NullPointer);
+            short_long_state_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn stats_merge_and_round_trip_through_bytes() {
+        let mut total = ShortLongStats::default();
+        for text in ["The cat sat", "on a magnificent mat"] {
+            let mut part = ShortLongStats::default();
+            let mut buf = [0; SHORT_LONG_STATS_MAX_ENCODED_LEN];
+            let mut len = 0;
+            unsafe {
+                short_long_stats(text.as_ptr(), text.len(), ptr::null(), &mut part);
+                let status = short_long_stats_encode(&part, buf.as_mut_ptr(), buf.len(), &mut len);
+                assert_eq!(status, ShortLongStatus::Ok);
+                let mut decoded = ShortLongStats::default();
+                let status = short_long_stats_decode(buf.as_ptr(), len, &mut decoded);
+                assert_eq!(status, ShortLongStatus::Ok);
+                let status = short_long_stats_merge(&total, &decoded, &mut total);
+                assert_eq!(status, ShortLongStatus::Ok);
+            }
+        }
+        let whole = crate::stats("The cat sat on a magnificent mat", &Config::default());
+        assert_eq!(Stats::from(total), whole);
+        assert_eq!(total.ratio, whole.ratio().unwrap());
+
+        let mut len = 0;
+        let status = unsafe { short_long_stats_encode(&total, [0u8; 2].as_mut_ptr(), 2, &mut len) };
+        assert_eq!((status, len), (ShortLongStatus::BufferTooSmall, 10));
+        let huge = ShortLongStats {
+            total_chars: u64::MAX,
+            ..ShortLongStats::default()
+        };
+        let before = Stats::from(total);
+        let status = unsafe { short_long_stats_merge(&total, &huge, &mut total) };
+        assert_eq!(status, ShortLongStatus::InvalidArgument);
+        assert_eq!(Stats::from(total), before);
+        let status = unsafe { short_long_stats_decode([9u8].as_ptr(), 1, &mut total) };
+        assert_eq!(status, ShortLongStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn null_text_is_empty_only_with_zero_length() {
+        let result = unsafe { short_long_result(ptr::null(), 0, ptr::null()) };
+        assert_eq!(result.status, ShortLongStatus::EmptyInput);
+        let result = unsafe { short_long_result(ptr::null(), 3, ptr::null()) };
+        assert_eq!(result.status, ShortLongStatus::NullPointer);
+
+        assert!(unsafe { short_long(ptr::null(), 0) }.is_nan());
+        assert!(unsafe { short_long_with_config(ptr::null(), 5, ptr::null()) }.is_nan());
+        let status = unsafe {
+            short_long_checked(
+                ptr::null(),
+                5,
+                ptr::null(),
+                false,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, ShortLongStatus::NullPointer);
+    }
+
+    #[test]
+    fn null_stopwords_are_rejected_unless_empty() {
+        let mut config = short_long_default_config();
+        config.stopwords = ptr::null();
+        config.stopwords_len = 0;
+        let result = unsafe { short_long_result(b"the".as_ptr(), 3, &config) };
+        assert_eq!(result.status, ShortLongStatus::Ok);
+
+        config.stopwords_len = 3;
+        let result = unsafe { short_long_result(b"the".as_ptr(), 3, &config) };
+        assert_eq!(result.status, ShortLongStatus::InvalidConfig);
+    }
+
+    #[test]
+    fn unknown_enum_values_are_rejected() {
+        let mut config = short_long_default_config();
+        config.tokenizer = Tokenizer::Legacy as u32;
+        let result = unsafe { short_long_result(b"the".as_ptr(), 3, &config) };
+        assert_eq!(result.status, ShortLongStatus::EmptyInput);
+
+        config.tokenizer = 2;
+        let result = unsafe { short_long_result(b"the".as_ptr(), 3, &config) };
+        assert_eq!(result.status, ShortLongStatus::InvalidConfig);
+
+        let mut config = short_long_default_config();
+        config.unit = LengthUnit::Bytes as u32;
+        let result = unsafe { short_long_result("naïveté".as_ptr(), 9, &config) };
+        assert_eq!(result.status, ShortLongStatus::Ok);
+        assert_eq!(result.ratio, 1.0);
+
+        config.unit = 3;
+        let result = unsafe { short_long_result("naïveté".as_ptr(), 9, &config) };
+        assert_eq!(result.status, ShortLongStatus::InvalidConfig);
+    }
+
+    #[test]
+    fn guard_turns_panics_into_a_status() {
+        assert_eq!(
+            guard(ShortLongStatus::Panic, || ShortLongStatus::Ok),
+            ShortLongStatus::Ok
+        );
+        let status = guard(ShortLongStatus::Panic, || -> ShortLongStatus {
+            panic!("boom");
+        });
+        assert_eq!(status, ShortLongStatus::Panic);
+    }
+}
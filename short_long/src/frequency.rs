@@ -0,0 +1,115 @@
+//! The most frequent words of a text.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::vec;
+
+use crate::diversity::Vocabulary;
+use crate::Config;
+
+/// Returns the `k` most frequent words of `text` with their counts, most
+/// frequent first and alphabetically among equals.
+///
+/// Words are tokens trimmed of leading and trailing punctuation, leaving out
+/// `config.stopwords` with or without theirs, as in
+/// [`Diversity`](crate::Diversity).
+/// They are compared the way stopwords are: exactly, or ignoring case if
+/// `config.case_sensitive` is false, in which case they are returned in
+/// lowercase.
+pub fn top_words<'a>(text: &'a str, config: &Config, k: usize) -> TopWords<'a> {
+    let Vocabulary { ids, counts, .. } = Vocabulary::new(text, config, config.case_sensitive);
+    let mut words: Vec<_> = ids
+        .into_iter()
+        .map(|(word, id)| (word, counts[id]))
+        .collect();
+    if k < words.len() {
+        // Only the top `k` need sorting.
+        if let Some(last) = k.checked_sub(1) {
+            words.select_nth_unstable_by(last, by_frequency);
+        }
+        words.truncate(k);
+    }
+    words.sort_unstable_by(by_frequency);
+    TopWords(words.into_iter())
+}
+
+/// Orders words by descending count, then alphabetically.
+fn by_frequency(a: &(Cow<str>, u64), b: &(Cow<str>, u64)) -> Ordering {
+    b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))
+}
+
+/// Iterator over the most frequent words of a text and their counts,
+/// created by [`top_words`].
+#[derive(Clone, Debug)]
+pub struct TopWords<'a>(vec::IntoIter<(Cow<'a, str>, u64)>);
+
+impl<'a> Iterator for TopWords<'a> {
+    type Item = (Cow<'a, str>, u64);
+
+    fn next(&mut self) -> Option<(Cow<'a, str>, u64)> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TopWords<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::corpus;
+
+    fn top(text: &str, config: &Config, k: usize) -> Vec<(String, u64)> {
+        top_words(text, config, k)
+            .map(|(word, count)| (word.into_owned(), count))
+            .collect()
+    }
+
+    #[test]
+    fn most_frequent_first_then_alphabetical() {
+        let text = "the cat, the dog. A dog; a Cat! the bird";
+        let words = |list: &[(&str, u64)]| -> Vec<(String, u64)> {
+            list.iter().map(|&(w, c)| (w.to_string(), c)).collect()
+        };
+        assert_eq!(
+            top(text, &Config::default(), 10),
+            words(&[("dog", 2), ("A", 1), ("Cat", 1), ("bird", 1), ("cat", 1)])
+        );
+        assert_eq!(
+            top(text, &Config::default(), 2),
+            words(&[("dog", 2), ("A", 1)])
+        );
+        assert!(top(text, &Config::default(), 0).is_empty());
+
+        let folded = Config {
+            case_sensitive: false,
+            ..Config::default()
+        };
+        assert_eq!(
+            top(text, &folded, 10),
+            words(&[("cat", 2), ("dog", 2), ("bird", 1)])
+        );
+        assert_eq!(top_words(text, &folded, 2).len(), 2);
+    }
+
+    #[test]
+    fn punctuated_stopwords_are_not_counted() {
+        let text = "the cat, the. the; a cat a: \"the\"";
+        assert_eq!(top(text, &Config::default(), 10), [("cat".to_string(), 2)]);
+    }
+
+    #[test]
+    fn corpus_top_words() {
+        let text = corpus();
+        let config = Config {
+            case_sensitive: false,
+            ..Config::default()
+        };
+        let expected = [("of", 5934), ("and", 5718), ("in", 4890)];
+        let expected: Vec<_> = expected.map(|(w, c)| (w.to_string(), c)).into();
+        assert_eq!(top(&text, &config, 3), expected);
+    }
+}
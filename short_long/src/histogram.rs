@@ -0,0 +1,37 @@
+//! Distribution of word lengths.
+
+use crate::Config;
+
+/// Counts the words of `text` by length, in `config.unit`, ignoring
+/// `config.stopwords`.
+///
+/// The result has `buckets` entries. Entry `n` counts the words of length
+/// `n`, except the last one, which counts every word at least that long so
+/// that very long tokens are not lost.
+pub fn histogram(text: &str, config: &Config, buckets: usize) -> Vec<u64> {
+    let mut counts = vec![0; buckets];
+    if let Some(last) = buckets.checked_sub(1) {
+        for word in config.tokenizer.tokens(text) {
+            if !config.is_stopword(word) {
+                counts[config.unit.len(word).min(last)] += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_bucket_collects_long_words() {
+        let text = "I am the walrus, indubitably";
+        assert_eq!(
+            histogram(text, &Config::default(), 8),
+            [0, 1, 1, 0, 0, 0, 0, 2]
+        );
+        assert_eq!(histogram(text, &Config::default(), 1), [4]);
+        assert!(histogram(text, &Config::default(), 0).is_empty());
+    }
+}
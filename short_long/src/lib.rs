@@ -1,17 +1,232 @@
-use std::slice;
-use std::str;
-#[no_mangle]
-pub extern "C" fn short_long(ptr: * const u8, len: usize)->f64{
-    unsafe{
-        let the_slice:&[u8] = slice::from_raw_parts(ptr,len);
-        let the_string = str::from_utf8_unchecked(the_slice);
-        let split:Vec<&str> = the_string.split(char::is_whitespace).filter(|&s| s != "the" && s != "a").collect();
-        let mut l = 0.0;
-        for i in &split{
-            if i.len() > 8{
-                l = l + 1.0;
-            }
-        }
-        return l / (split.len() as f64);
-    }
-}
+//! Measures how long-winded a piece of text is: the fraction of its words
+//! that are longer than 8 characters, ignoring "the" and "a".
+//!
+//! [`ratio`] is the safe Rust entry point and [`ratio_with`] takes a
+//! [`Config`] to change the threshold and stopwords. [`try_ratio_with`]
+//! reports an [`Error`] where those return NaN, [`stats`] returns the
+//! underlying counts, [`Stats::readability`] the standard [`Readability`]
+//! indices from the same pass, and [`histogram`] the full distribution of
+//! word lengths. [`polysyllabic_ratio`] measures long words by [`syllables`]
+//! instead of length. [`sentences`] splits text into sentences and
+//! [`sentence_stats`] counts each one. [`diversity`] measures how varied the
+//! vocabulary is and [`top_words`] lists its most frequent words.
+//! [`ratio_batch`] scores many documents at once, and [`stats_parallel`] and
+//! [`ratio_batch_parallel`] do the same work on several threads.
+//! [`Accumulator`] counts text that arrives in chunks, and [`Stats`] from
+//! separate texts or processes can be added and encoded to bytes.
+//! [`Summary`] describes the spread of many ratios, such as one per line.
+//! The C-ABI exports used from Python through CFFI live in [`ffi`] and are
+//! thin wrappers over them. With the `python` feature, the crate also builds
+//! as a native `short_long` Python module.
+
+use std::str;
+
+mod batch;
+mod config;
+mod diversity;
+mod error;
+pub mod ffi;
+mod frequency;
+mod histogram;
+mod parallel;
+#[cfg(feature = "python")]
+mod python;
+mod readability;
+mod scan;
+mod sentence;
+mod stats;
+mod stream;
+mod summary;
+mod syllable;
+mod tokenize;
+
+pub use crate::batch::{ratio_batch, split_offsets};
+pub use crate::config::{Config, Stopwords};
+pub use crate::diversity::{diversity, Diversity, DiversityOptions};
+pub use crate::error::Error;
+pub use crate::frequency::{top_words, TopWords};
+pub use crate::histogram::histogram;
+pub use crate::parallel::{ratio_batch_parallel, stats_parallel};
+pub use crate::readability::Readability;
+pub use crate::sentence::{sentence_stats, sentences, Sentences};
+pub use crate::stats::Stats;
+pub use crate::stream::{stats_from_chunks, Accumulator};
+pub use crate::summary::Summary;
+pub use crate::syllable::syllables;
+pub use crate::tokenize::{is_python_whitespace, LengthUnit, Tokenizer, Tokens, UnknownName};
+
+/// Returns the fraction of words in `text` that are longer than 8
+/// characters, ignoring "the" and "a", or NaN if there are no such words.
+pub fn ratio(text: &str) -> f64 {
+    ratio_with(text, &Config::default())
+}
+
+/// Returns the fraction of words in `text` with three or more
+/// [`syllables`], or NaN if it has no words. Unlike [`ratio`], this counts
+/// stopwords.
+pub fn polysyllabic_ratio(text: &str) -> f64 {
+    stats(text, &Config::default())
+        .polysyllabic_ratio()
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the fraction of words in `text` that are longer than
+/// `config.threshold` in `config.unit`, ignoring `config.stopwords`, or NaN
+/// if no words are left.
+pub fn ratio_with(text: &str, config: &Config) -> f64 {
+    try_ratio_with(text, config).unwrap_or(f64::NAN)
+}
+
+/// Like [`ratio_with`], but returns [`Error::EmptyInput`] instead of NaN
+/// when no words are left after removing stopwords.
+pub fn try_ratio_with(text: &str, config: &Config) -> Result<f64, Error> {
+    // Skip the readability counts, which would double the time taken.
+    let mut stats = Stats::default();
+    for word in config.tokenizer.tokens(text) {
+        stats.add_ratio_word(word, config);
+    }
+    stats.ratio()
+}
+
+/// Counts the words of `text` that [`ratio_with`] divides, along with what
+/// [`Stats::readability`] needs.
+///
+/// This is a single pass over the text. It does not allocate unless
+/// `config.case_sensitive` is false, in which case each word containing
+/// uppercase letters is lowercased into a new string to match stopwords.
+pub fn stats(text: &str, config: &Config) -> Stats {
+    let mut stats = Stats::default();
+    for word in config.tokenizer.tokens(text) {
+        stats.add_word(word, config);
+    }
+    stats
+}
+
+/// Like [`stats`], but takes raw bytes and checks that they are valid UTF-8
+/// first.
+pub fn stats_bytes(bytes: &[u8], config: &Config) -> Result<Stats, Error> {
+    Ok(stats(str::from_utf8(bytes)?, config))
+}
+
+/// Like [`try_ratio_with`], but takes raw bytes and checks that they are
+/// valid UTF-8 first.
+pub fn ratio_bytes(bytes: &[u8], config: &Config) -> Result<f64, Error> {
+    try_ratio_with(str::from_utf8(bytes)?, config)
+}
+
+/// Like [`ratio_bytes`], but replaces invalid UTF-8 sequences with U+FFFD
+/// instead of failing on them.
+pub fn ratio_bytes_lossy(bytes: &[u8], config: &Config) -> Result<f64, Error> {
+    try_ratio_with(&String::from_utf8_lossy(bytes), config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    /// The text of `formalSentences.txt`, which the tests share.
+    pub(crate) fn corpus() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../formalSentences.txt");
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn counts_words_longer_than_eight_chars() {
+        assert_eq!(ratio("tiny enormously"), 0.5);
+        assert_eq!(ratio("eightchr ninechars"), 0.5);
+    }
+
+    #[test]
+    fn ignores_the_and_a() {
+        assert_eq!(ratio("the a cat magnificent"), 0.5);
+    }
+
+    #[test]
+    fn threshold_is_configurable() {
+        let text = "tiny sixsix enormously";
+        assert_eq!(ratio_with(text, &Config::with_threshold(5)), 2.0 / 3.0);
+        assert_eq!(ratio_with(text, &Config::with_threshold(12)), 0.0);
+    }
+
+    #[test]
+    fn stopwords_can_ignore_case() {
+        let mut config = Config::default();
+        config.stopwords.insert("Of".to_string());
+        assert_eq!(ratio_with("The OF of magnificent", &config), 0.25);
+        config.case_sensitive = false;
+        assert_eq!(ratio_with("The OF of magnificent", &config), 1.0);
+
+        // Both the stopwords and the words are folded, beyond ASCII too.
+        let config = Config {
+            stopwords: ["ÉTÉ", "Straße"].iter().map(|s| s.to_string()).collect(),
+            case_sensitive: false,
+            ..Config::default()
+        };
+        assert!(["été", "Été", "STRAßE", "straße"]
+            .iter()
+            .all(|word| config.is_stopword(word)));
+        assert!(!config.is_stopword("ete"));
+    }
+
+    #[test]
+    fn length_unit_is_configurable() {
+        let text = "cat élégantes";
+        let mut config = Config::default();
+        assert_eq!(ratio_with(text, &config), 0.5);
+        config.threshold = 9;
+        assert_eq!(ratio_with(text, &config), 0.0);
+        config.unit = LengthUnit::Bytes;
+        assert_eq!(ratio_with(text, &config), 0.5);
+    }
+
+    #[test]
+    fn stats_keep_numerator_and_denominator() {
+        let stats = stats("The cat sat on a magnificent mat", &Config::default());
+        assert_eq!(
+            stats,
+            Stats {
+                total_tokens: 7,
+                filtered_tokens: 6,
+                long_tokens: 1,
+                total_chars: 25,
+                words: 7,
+                sentences: 0,
+                syllables: 10,
+                polysyllables: 1,
+                letters: 26,
+            }
+        );
+        assert_eq!(stats.ratio(), Ok(1.0 / 6.0));
+    }
+
+    #[test]
+    fn legacy_tokenizer_counts_empty_words() {
+        let mut config = Config::default();
+        assert_eq!(ratio_with("cat  enormously", &config), 0.5);
+        config.tokenizer = Tokenizer::Legacy;
+        assert_eq!(ratio_with("cat  enormously", &config), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn bytes_must_be_utf8() {
+        let config = Config::default();
+        assert_eq!(ratio_bytes(b"tiny enormously", &config), Ok(0.5));
+        assert_eq!(
+            ratio_bytes(&[b'a', 0xff], &config),
+            Err(Error::InvalidUtf8 { valid_up_to: 1 })
+        );
+        assert_eq!(ratio_bytes_lossy(b"caf\xe9 enormously", &config), Ok(0.5));
+    }
+
+    #[test]
+    fn empty_input_is_an_error() {
+        let config = Config::default();
+        assert_eq!(try_ratio_with("", &config), Err(Error::EmptyInput));
+        assert_eq!(try_ratio_with(" \n ", &config), Err(Error::EmptyInput));
+        assert_eq!(try_ratio_with("the a", &config), Err(Error::EmptyInput));
+        assert_eq!(try_ratio_with("cat", &config), Ok(0.0));
+        assert!(ratio("the a").is_nan());
+    }
+}
@@ -0,0 +1,142 @@
+//! Spreading the work over several threads.
+//!
+//! Word counts add up, so a text can be cut at whitespace into pieces that
+//! are counted separately and merged; the final ratio is computed from the
+//! merged integer counts and is therefore identical to the serial one.
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::{is_python_whitespace, Config, Error, Stats, Tokenizer};
+
+/// Texts shorter than this many bytes per thread are not worth splitting.
+const MIN_CHUNK: usize = 64 * 1024;
+
+/// Resolves a requested thread count, where 0 means one per available CPU.
+fn thread_count(threads: usize) -> usize {
+    match threads {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    }
+}
+
+/// Like [`stats`](crate::stats), but counts `text` on up to `threads`
+/// threads, or one per CPU if `threads` is 0.
+///
+/// The text is only split at whitespace, and only with
+/// [`Tokenizer::Python`]: the legacy tokenizer turns every whitespace
+/// character into a word boundary, so splitting would change its counts.
+pub fn stats_parallel(text: &str, config: &Config, threads: usize) -> Stats {
+    let threads = thread_count(threads).min(text.len() / MIN_CHUNK).max(1);
+    if threads == 1 || config.tokenizer != Tokenizer::Python {
+        return crate::stats(text, config);
+    }
+    let chunks = split_at_whitespace(text, threads);
+    thread::scope(|s| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| s.spawn(move || crate::stats(chunk, config)))
+            .collect();
+        let mut total = Stats::default();
+        for handle in handles {
+            total.merge(&handle.join().unwrap());
+        }
+        total
+    })
+}
+
+/// Like [`ratio_batch`](crate::ratio_batch), but spreads the documents over
+/// up to `threads` threads, or one per CPU if `threads` is 0. Results stay
+/// in document order.
+pub fn ratio_batch_parallel<D: AsRef<[u8]> + Sync>(
+    docs: &[D],
+    config: &Config,
+    threads: usize,
+) -> Vec<Result<f64, Error>> {
+    let threads = thread_count(threads).min(docs.len()).max(1);
+    if threads == 1 {
+        return crate::ratio_batch(docs, config);
+    }
+    let per_thread = docs.len().div_ceil(threads);
+    thread::scope(|s| {
+        let handles: Vec<_> = docs
+            .chunks(per_thread)
+            .map(|docs| s.spawn(move || crate::ratio_batch(docs, config)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Cuts `text` into at most `pieces` parts of roughly equal size, each
+/// ending just before a whitespace character so that no word is split.
+fn split_at_whitespace(text: &str, pieces: usize) -> Vec<&str> {
+    let target = text.len() / pieces;
+    let mut chunks = Vec::with_capacity(pieces);
+    let mut rest = text;
+    while chunks.len() + 1 < pieces && rest.len() > target {
+        let mut cut = target;
+        while !rest.is_char_boundary(cut) {
+            cut += 1;
+        }
+        match rest[cut..].find(is_python_whitespace) {
+            Some(i) => {
+                let (chunk, tail) = rest.split_at(cut + i);
+                chunks.push(chunk);
+                rest = tail;
+            }
+            None => break,
+        }
+    }
+    chunks.push(rest);
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::corpus;
+
+    #[test]
+    fn pieces_cover_the_text_without_splitting_words() {
+        let text = "one two\u{a0}three  four five";
+        for pieces in 1..8 {
+            let chunks = split_at_whitespace(text, pieces);
+            assert!(chunks.len() <= pieces);
+            assert_eq!(chunks.concat(), text);
+            let words: usize = chunks
+                .iter()
+                .map(|c| Tokenizer::Python.tokens(c).count())
+                .sum();
+            assert_eq!(words, 5);
+        }
+    }
+
+    #[test]
+    fn parallel_stats_match_serial_on_corpus() {
+        let text = corpus();
+        let config = Config::default();
+        let serial = crate::stats(&text, &config);
+        for threads in [0, 2, 3, 7] {
+            let parallel = stats_parallel(&text, &config, threads);
+            assert_eq!(parallel, serial);
+            assert_eq!(
+                parallel.ratio().unwrap().to_bits(),
+                serial.ratio().unwrap().to_bits()
+            );
+        }
+    }
+
+    #[test]
+    fn parallel_batch_keeps_document_order() {
+        let text = corpus();
+        let docs: Vec<&str> = text.lines().collect();
+        let config = Config::default();
+        let serial = crate::ratio_batch(&docs, &config);
+        for threads in [0, 2, 5] {
+            assert_eq!(ratio_batch_parallel(&docs, &config, threads), serial);
+        }
+    }
+}
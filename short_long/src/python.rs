@@ -0,0 +1,258 @@
+//! The `short_long` Python extension module, built with the `python`
+//! feature.
+//!
+//! Unlike the CFFI exports in [`ffi`](crate::ffi), these functions take a
+//! `str` or `bytes` object directly and read it in place, and raise
+//! `ValueError` where the C functions return a status.
+
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyOverflowError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+
+use crate::{Config, Error, Stats, UnknownName};
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+impl From<UnknownName> for PyErr {
+    fn from(e: UnknownName) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// Borrows the text of a `str` or UTF-8 `bytes` object without copying it.
+fn text_of<'a>(text: &'a Bound<'_, PyAny>) -> PyResult<&'a str> {
+    if let Ok(text) = text.cast::<PyString>() {
+        text.to_str()
+    } else if let Ok(text) = text.cast::<PyBytes>() {
+        Ok(std::str::from_utf8(text.as_bytes()).map_err(Error::from)?)
+    } else {
+        Err(PyTypeError::new_err("expected str or bytes"))
+    }
+}
+
+/// Python-visible [`Config`]. Any argument left out keeps its default.
+#[pyclass(name = "Config", module = "short_long", frozen)]
+struct PyConfig(Config);
+
+#[pymethods]
+impl PyConfig {
+    #[new]
+    #[pyo3(signature = (*, threshold=None, stopwords=None, case_sensitive=None, unit=None, tokenizer=None))]
+    fn new(
+        threshold: Option<usize>,
+        stopwords: Option<Vec<String>>,
+        case_sensitive: Option<bool>,
+        unit: Option<&str>,
+        tokenizer: Option<&str>,
+    ) -> PyResult<PyConfig> {
+        let mut config = Config::default();
+        if let Some(threshold) = threshold {
+            config.threshold = threshold;
+        }
+        if let Some(stopwords) = stopwords {
+            config.stopwords = stopwords.into_iter().collect();
+        }
+        if let Some(case_sensitive) = case_sensitive {
+            config.case_sensitive = case_sensitive;
+        }
+        if let Some(unit) = unit {
+            config.unit = unit.parse()?;
+        }
+        if let Some(tokenizer) = tokenizer {
+            config.tokenizer = tokenizer.parse()?;
+        }
+        Ok(PyConfig(config))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Python-visible [`Stats`].
+#[pyclass(name = "Stats", module = "short_long", frozen)]
+struct PyStats(Stats);
+
+#[pymethods]
+impl PyStats {
+    #[getter]
+    fn total_tokens(&self) -> u64 {
+        self.0.total_tokens
+    }
+
+    #[getter]
+    fn filtered_tokens(&self) -> u64 {
+        self.0.filtered_tokens
+    }
+
+    #[getter]
+    fn long_tokens(&self) -> u64 {
+        self.0.long_tokens
+    }
+
+    #[getter]
+    fn total_chars(&self) -> u64 {
+        self.0.total_chars
+    }
+
+    #[getter]
+    fn words(&self) -> u64 {
+        self.0.words
+    }
+
+    #[getter]
+    fn sentences(&self) -> u64 {
+        self.0.sentences
+    }
+
+    #[getter]
+    fn syllables(&self) -> u64 {
+        self.0.syllables
+    }
+
+    #[getter]
+    fn polysyllables(&self) -> u64 {
+        self.0.polysyllables
+    }
+
+    #[getter]
+    fn letters(&self) -> u64 {
+        self.0.letters
+    }
+
+    /// The fraction of words with three or more syllables, or `None` if the
+    /// text has no words.
+    #[getter]
+    fn polysyllabic_ratio(&self) -> Option<f64> {
+        self.0.polysyllabic_ratio().ok()
+    }
+
+    /// The average sentence length in words, or `None` if the text has no
+    /// words.
+    #[getter]
+    fn words_per_sentence(&self) -> Option<f64> {
+        self.0.words_per_sentence().ok()
+    }
+
+    /// The readability indices as a dict keyed by name, or `None` if the
+    /// text has no words.
+    #[getter]
+    fn readability(&self) -> Option<HashMap<&'static str, f64>> {
+        let r = self.0.readability().ok()?;
+        Some(HashMap::from([
+            ("flesch_reading_ease", r.flesch_reading_ease),
+            ("flesch_kincaid_grade", r.flesch_kincaid_grade),
+            ("gunning_fog", r.gunning_fog),
+            ("smog", r.smog),
+            ("coleman_liau", r.coleman_liau),
+            ("automated_readability", r.automated_readability),
+        ]))
+    }
+
+    /// The ratio, or `None` if no words were left after removing stopwords.
+    #[getter]
+    fn ratio(&self) -> Option<f64> {
+        self.0.ratio().ok()
+    }
+
+    /// Serializes the counts compactly, for combining results from other
+    /// processes.
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.encode()
+    }
+
+    /// Parses the output of `to_bytes`.
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<PyStats> {
+        Stats::decode(bytes)
+            .map(PyStats)
+            .ok_or_else(|| PyValueError::new_err("not an encoded Stats"))
+    }
+
+    /// Adds the counts of two texts, as if they had been counted together.
+    /// Raises `OverflowError` if a count does not fit in 64 bits.
+    fn __add__(&self, other: &PyStats) -> PyResult<PyStats> {
+        self.0
+            .checked_add(&other.0)
+            .map(PyStats)
+            .ok_or_else(|| PyOverflowError::new_err("Stats counts overflow"))
+    }
+
+    fn __eq__(&self, other: &PyStats) -> bool {
+        self.0 == other.0
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Runs `f` on `config`, or on the default preset if it is `None`.
+fn with_config<T>(config: Option<&Bound<'_, PyConfig>>, f: impl FnOnce(&Config) -> T) -> T {
+    match config {
+        Some(config) => f(&config.get().0),
+        None => f(&Config::default()),
+    }
+}
+
+/// Returns the fraction of words in `text` that are long. Raises
+/// `ValueError` if no words are left after removing stopwords or if `bytes`
+/// are not UTF-8.
+#[pyfunction]
+#[pyo3(signature = (text, config=None))]
+fn short_long(
+    py: Python<'_>,
+    text: &Bound<'_, PyAny>,
+    config: Option<&Bound<'_, PyConfig>>,
+) -> PyResult<f64> {
+    let text = text_of(text)?;
+    Ok(with_config(config, |config| {
+        py.detach(|| crate::try_ratio_with(text, config))
+    })?)
+}
+
+/// Returns the counts behind the ratio for `text`.
+#[pyfunction]
+#[pyo3(signature = (text, config=None))]
+fn stats(
+    py: Python<'_>,
+    text: &Bound<'_, PyAny>,
+    config: Option<&Bound<'_, PyConfig>>,
+) -> PyResult<PyStats> {
+    let text = text_of(text)?;
+    let stats = with_config(config, |config| py.detach(|| crate::stats(text, config)));
+    Ok(PyStats(stats))
+}
+
+/// Returns the number of words of each length in `text`, with the last of
+/// the `buckets` entries counting every longer word.
+#[pyfunction]
+#[pyo3(signature = (text, buckets, config=None))]
+fn histogram(
+    py: Python<'_>,
+    text: &Bound<'_, PyAny>,
+    buckets: usize,
+    config: Option<&Bound<'_, PyConfig>>,
+) -> PyResult<Vec<u64>> {
+    let text = text_of(text)?;
+    Ok(with_config(config, |config| {
+        py.detach(|| crate::histogram(text, config, buckets))
+    }))
+}
+
+#[pymodule]
+#[pyo3(name = "short_long")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PyStats>()?;
+    m.add_function(wrap_pyfunction!(short_long, m)?)?;
+    m.add_function(wrap_pyfunction!(stats, m)?)?;
+    m.add_function(wrap_pyfunction!(histogram, m)?)?;
+    Ok(())
+}
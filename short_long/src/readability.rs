@@ -0,0 +1,181 @@
+//! Standard readability indices, computed from the counts in [`Stats`].
+
+use crate::sentence::{is_abbreviation, CLOSERS};
+use crate::syllable::Counter;
+use crate::{Error, Stats};
+
+/// Readability scores of a text.
+///
+/// Every index follows its usual published formula, with "words" being the
+/// tokens that contain a letter or digit, "characters" being letters and
+/// digits, and "complex words" those of three or more syllables. Stopwords
+/// and the long-word threshold do not affect them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Readability {
+    /// Flesch Reading Ease: higher is easier, 60–70 is plain English.
+    pub flesch_reading_ease: f64,
+    /// Flesch–Kincaid Grade Level: the US school grade needed to follow the
+    /// text.
+    pub flesch_kincaid_grade: f64,
+    /// Gunning Fog index, counting every word of three or more syllables as
+    /// complex.
+    pub gunning_fog: f64,
+    /// SMOG grade, scaled to a 30-sentence sample.
+    pub smog: f64,
+    /// Coleman–Liau index, from letters rather than syllables.
+    pub coleman_liau: f64,
+    /// Automated Readability Index, from characters per word.
+    pub automated_readability: f64,
+}
+
+impl Readability {
+    /// Computes every index from `stats`, or returns
+    /// [`Error::EmptyInput`] if the text has no words. A text with words but
+    /// no sentence terminator counts as one sentence.
+    pub fn new(stats: &Stats) -> Result<Readability, Error> {
+        let words_per_sentence = stats.words_per_sentence()?;
+        let words = stats.words as f64;
+        let sentences = stats.sentences.max(1) as f64;
+        let syllables_per_word = stats.syllables as f64 / words;
+        let polysyllables = stats.polysyllables as f64;
+        let letters_per_word = stats.letters as f64 / words;
+        Ok(Readability {
+            flesch_reading_ease: 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+            flesch_kincaid_grade: 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+            gunning_fog: 0.4 * (words_per_sentence + 100.0 * polysyllables / words),
+            smog: 1.043 * (polysyllables * 30.0 / sentences).sqrt() + 3.1291,
+            coleman_liau: 0.0588 * 100.0 * letters_per_word
+                - 0.296 * 100.0 / words_per_sentence
+                - 15.8,
+            automated_readability: 4.71 * letters_per_word + 0.5 * words_per_sentence - 21.43,
+        })
+    }
+}
+
+/// What one word contributes to the readability counts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Word {
+    /// Letters and digits; a token without any is not a word.
+    pub(crate) letters: usize,
+    pub(crate) syllables: usize,
+    pub(crate) ends_sentence: bool,
+}
+
+/// Measures `word` in one pass over its bytes.
+#[inline]
+pub(crate) fn measure(word: &str) -> Word {
+    let mut letters = 0;
+    let mut syllables = Counter::default();
+    let mut non_ascii = false;
+    for b in word.bytes() {
+        letters += b.is_ascii_alphanumeric() as usize;
+        non_ascii |= !b.is_ascii();
+        syllables.push(b);
+    }
+    if non_ascii {
+        letters = word.chars().filter(|c| c.is_alphanumeric()).count();
+    }
+    Word {
+        letters,
+        syllables: syllables.finish(),
+        ends_sentence: ends_sentence(word),
+    }
+}
+
+/// Whether `word` ends a sentence: it ends in `.`, `!`, `?` or `…`, possibly
+/// followed by closing quotes or brackets, and is not an abbreviation as
+/// [`sentences`](crate::sentences) sees them. Unlike that, it cannot look
+/// at the next word.
+fn ends_sentence(word: &str) -> bool {
+    // Most words end in a letter, so skip the general check for them.
+    if word
+        .as_bytes()
+        .last()
+        .is_some_and(u8::is_ascii_alphanumeric)
+    {
+        return false;
+    }
+    let rest = word.trim_end_matches(CLOSERS);
+    match rest.chars().next_back() {
+        Some('.') => {
+            let word = &rest[..rest.len() - 1];
+            word.ends_with('.') || !is_abbreviation(word)
+        }
+        Some('!' | '?' | '…') => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn known_scores() {
+        let text = "The cat sat on the mat. It was a beautiful, extraordinary day!";
+        let stats = crate::stats(text, &Config::default());
+        assert_eq!(
+            (
+                stats.words,
+                stats.sentences,
+                stats.syllables,
+                stats.polysyllables,
+                stats.letters
+            ),
+            (12, 2, 18, 2, 48)
+        );
+        assert_eq!(stats.words_per_sentence(), Ok(6.0));
+        let r = stats.readability().unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(
+            r.flesch_reading_ease,
+            206.835 - 1.015 * 6.0 - 84.6 * 18.0 / 12.0
+        ));
+        assert!(close(
+            r.flesch_kincaid_grade,
+            0.39 * 6.0 + 11.8 * 18.0 / 12.0 - 15.59
+        ));
+        assert!(close(r.gunning_fog, 0.4 * (6.0 + 100.0 * 2.0 / 12.0)));
+        assert!(close(r.smog, 1.043 * 30f64.sqrt() + 3.1291));
+        assert!(close(
+            r.coleman_liau,
+            0.0588 * 400.0 - 0.296 * 100.0 / 6.0 - 15.8
+        ));
+        assert!(close(
+            r.automated_readability,
+            4.71 * 4.0 + 0.5 * 6.0 - 21.43
+        ));
+    }
+
+    #[test]
+    fn words_need_letters() {
+        let stats = crate::stats("— ... !", &Config::default());
+        assert_eq!(stats.words, 0);
+        assert_eq!(stats.readability(), Err(Error::EmptyInput));
+        let unterminated = crate::stats("no full stop here", &Config::default());
+        assert_eq!(unterminated.sentences, 0);
+        assert_eq!(unterminated.words_per_sentence(), Ok(4.0));
+        assert_eq!(stats.words_per_sentence(), Err(Error::EmptyInput));
+        assert!(unterminated.readability().is_ok());
+    }
+
+    #[test]
+    fn sentence_ends() {
+        for word in ["end.", "end!\"", "what?)", "so…", "“Yes.”", "so...", "US."] {
+            assert!(ends_sentence(word), "{}", word);
+        }
+        for word in [
+            "mid",
+            "3.14",
+            "e.g",
+            "\"quote\"",
+            "",
+            "Dr.",
+            "U.S.",
+            "(e.g.)",
+        ] {
+            assert!(!ends_sentence(word), "{}", word);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Fast word boundaries for [`Tokenizer::Python`](crate::Tokenizer).
+//!
+//! Finding words only needs each byte classified as whitespace or not, so
+//! runs of ASCII are classified 16 or 32 bytes at a time with SSE2 or AVX2,
+//! chosen at runtime. Non-ASCII bytes stop the vector scan and the character
+//! they start is decoded and checked with [`is_python_whitespace`] instead,
+//! so the words found are exactly those of `str::split(is_python_whitespace)`
+//! with empty words dropped.
+
+use crate::is_python_whitespace;
+
+/// Whether `b` is an ASCII byte for which [`is_python_whitespace`] is true.
+fn is_ascii_space(b: u8) -> bool {
+    matches!(b, b'\t'..=b'\r' | 0x1c..=0x1f | b' ')
+}
+
+/// A pair of boundary searches over bytes, all starting at `from` and
+/// returning `bytes.len()` if nothing is found.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Scanner {
+    /// Finds the first byte that is not ASCII whitespace.
+    skip_space: fn(&[u8], usize) -> usize,
+    /// Finds the first byte that is ASCII whitespace or not ASCII at all.
+    skip_word: fn(&[u8], usize) -> usize,
+}
+
+impl Scanner {
+    /// The portable byte-at-a-time implementation.
+    #[cfg(any(test, not(target_arch = "x86_64")))]
+    pub(crate) const SCALAR: Scanner = Scanner {
+        skip_space: scalar::skip_space,
+        skip_word: scalar::skip_word,
+    };
+
+    /// Returns the fastest implementation this CPU supports.
+    pub(crate) fn detect() -> Scanner {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return x86::AVX2;
+            }
+            x86::SSE2
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        Scanner::SCALAR
+    }
+}
+
+/// Iterator over the words of a string, split like Python's `str.split()`.
+#[derive(Clone, Debug)]
+pub(crate) struct Words<'a> {
+    text: &'a str,
+    pos: usize,
+    scanner: Scanner,
+}
+
+impl<'a> Words<'a> {
+    pub(crate) fn new(text: &'a str, scanner: Scanner) -> Words<'a> {
+        Words {
+            text,
+            pos: 0,
+            scanner,
+        }
+    }
+
+    /// Returns the character starting at byte `i`, which must not be ASCII.
+    fn char_at(&self, i: usize) -> char {
+        self.text[i..].chars().next().unwrap()
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let bytes = self.text.as_bytes();
+        let start = loop {
+            let i = (self.scanner.skip_space)(bytes, self.pos);
+            if i == bytes.len() {
+                self.pos = i;
+                return None;
+            }
+            if bytes[i].is_ascii() {
+                break i;
+            }
+            let c = self.char_at(i);
+            if !is_python_whitespace(c) {
+                break i;
+            }
+            self.pos = i + c.len_utf8();
+        };
+        let mut end = start;
+        loop {
+            end = (self.scanner.skip_word)(bytes, end);
+            if end == bytes.len() || bytes[end].is_ascii() {
+                break;
+            }
+            let c = self.char_at(end);
+            if is_python_whitespace(c) {
+                break;
+            }
+            end += c.len_utf8();
+        }
+        self.pos = end;
+        Some(&self.text[start..end])
+    }
+}
+
+mod scalar {
+    use super::is_ascii_space;
+
+    pub(super) fn skip_space(bytes: &[u8], from: usize) -> usize {
+        bytes[from..]
+            .iter()
+            .position(|&b| !is_ascii_space(b))
+            .map_or(bytes.len(), |i| from + i)
+    }
+
+    pub(super) fn skip_word(bytes: &[u8], from: usize) -> usize {
+        bytes[from..]
+            .iter()
+            .position(|&b| is_ascii_space(b) || !b.is_ascii())
+            .map_or(bytes.len(), |i| from + i)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::{scalar, Scanner};
+
+    pub(super) const SSE2: Scanner = Scanner {
+        skip_space: |bytes, from| unsafe { sse2_scan(bytes, from, true) },
+        skip_word: |bytes, from| unsafe { sse2_scan(bytes, from, false) },
+    };
+
+    pub(super) const AVX2: Scanner = Scanner {
+        skip_space: |bytes, from| unsafe { avx2_scan(bytes, from, true) },
+        skip_word: |bytes, from| unsafe { avx2_scan(bytes, from, false) },
+    };
+
+    /// Finds the first byte at or after `from` that is not ASCII whitespace
+    /// (`space` set) or that is ASCII whitespace or not ASCII (`space`
+    /// unset), 16 bytes at a time.
+    ///
+    /// Bytes are compared as signed, so every non-ASCII byte is negative and
+    /// falls outside the whitespace ranges.
+    ///
+    /// # Safety
+    ///
+    /// SSE2 must be available, which it always is on x86_64.
+    #[target_feature(enable = "sse2")]
+    unsafe fn sse2_scan(bytes: &[u8], from: usize, space: bool) -> usize {
+        let mut i = from;
+        while i + 16 <= bytes.len() {
+            let v = _mm_loadu_si128(bytes.as_ptr().add(i) as *const __m128i);
+            let controls = _mm_and_si128(
+                _mm_cmpgt_epi8(v, _mm_set1_epi8(0x08)),
+                _mm_cmplt_epi8(v, _mm_set1_epi8(0x0e)),
+            );
+            let separators = _mm_and_si128(
+                _mm_cmpgt_epi8(v, _mm_set1_epi8(0x1b)),
+                _mm_cmplt_epi8(v, _mm_set1_epi8(0x20)),
+            );
+            let spaces = _mm_cmpeq_epi8(v, _mm_set1_epi8(0x20));
+            let ws =
+                _mm_movemask_epi8(_mm_or_si128(_mm_or_si128(controls, separators), spaces)) as u32;
+            let stop = if space {
+                !ws & 0xffff
+            } else {
+                ws | _mm_movemask_epi8(v) as u32
+            };
+            if stop != 0 {
+                return i + stop.trailing_zeros() as usize;
+            }
+            i += 16;
+        }
+        tail(bytes, i, space)
+    }
+
+    /// Like [`sse2_scan`], but 32 bytes at a time.
+    ///
+    /// # Safety
+    ///
+    /// AVX2 must be available.
+    #[target_feature(enable = "avx2")]
+    unsafe fn avx2_scan(bytes: &[u8], from: usize, space: bool) -> usize {
+        let mut i = from;
+        while i + 32 <= bytes.len() {
+            let v = _mm256_loadu_si256(bytes.as_ptr().add(i) as *const __m256i);
+            let controls = _mm256_and_si256(
+                _mm256_cmpgt_epi8(v, _mm256_set1_epi8(0x08)),
+                _mm256_cmpgt_epi8(_mm256_set1_epi8(0x0e), v),
+            );
+            let separators = _mm256_and_si256(
+                _mm256_cmpgt_epi8(v, _mm256_set1_epi8(0x1b)),
+                _mm256_cmpgt_epi8(_mm256_set1_epi8(0x20), v),
+            );
+            let spaces = _mm256_cmpeq_epi8(v, _mm256_set1_epi8(0x20));
+            let ws = _mm256_movemask_epi8(_mm256_or_si256(
+                _mm256_or_si256(controls, separators),
+                spaces,
+            )) as u32;
+            let stop = if space {
+                !ws
+            } else {
+                ws | _mm256_movemask_epi8(v) as u32
+            };
+            if stop != 0 {
+                return i + stop.trailing_zeros() as usize;
+            }
+            i += 32;
+        }
+        tail(bytes, i, space)
+    }
+
+    /// Finishes a scan over the last, partial block.
+    fn tail(bytes: &[u8], from: usize, space: bool) -> usize {
+        if space {
+            scalar::skip_space(bytes, from)
+        } else {
+            scalar::skip_word(bytes, from)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift generator, so the tests are reproducible without
+    /// extra dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    /// Builds a string mixing ASCII and non-ASCII words and whitespace, in
+    /// runs long enough to cross vector block boundaries.
+    fn random_text(rng: &mut Rng) -> String {
+        const PIECES: &[&str] = &[
+            "a", "word", "x", "é", "中文", "🦀", "\u{301}", " ", "  ", "\t", "\n", "\r\n", "\x0b",
+            "\x0c", "\x1c", "\x1f", "\x08", "\x0e", "\x1b", "!", "\u{a0}", "\u{85}", "\u{2003}",
+            "\u{3000}", "\u{200b}",
+        ];
+        let mut text = String::new();
+        for _ in 0..rng.below(200) {
+            let piece = PIECES[rng.below(PIECES.len())];
+            for _ in 0..=rng.below(40) / 30 * rng.below(40) {
+                text.push_str(piece);
+            }
+        }
+        text
+    }
+
+    fn reference(text: &str) -> Vec<&str> {
+        text.split(is_python_whitespace)
+            .filter(|w| !w.is_empty())
+            .collect()
+    }
+
+    fn scanners() -> Vec<Scanner> {
+        let mut scanners = vec![Scanner::SCALAR, Scanner::detect()];
+        #[cfg(target_arch = "x86_64")]
+        {
+            scanners.push(x86::SSE2);
+            if is_x86_feature_detected!("avx2") {
+                scanners.push(x86::AVX2);
+            }
+        }
+        scanners
+    }
+
+    #[test]
+    fn boundaries_match_scalar() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let text = random_text(&mut rng);
+            let bytes = text.as_bytes();
+            for from in 0..=bytes.len() {
+                for scanner in scanners() {
+                    assert_eq!(
+                        (scanner.skip_space)(bytes, from),
+                        scalar::skip_space(bytes, from)
+                    );
+                    assert_eq!(
+                        (scanner.skip_word)(bytes, from),
+                        scalar::skip_word(bytes, from)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn words_match_split() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2000 {
+            let text = random_text(&mut rng);
+            for scanner in scanners() {
+                let words: Vec<_> = Words::new(&text, scanner).collect();
+                assert_eq!(words, reference(&text), "in {:?}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn every_byte_is_classified_like_python() {
+        for b in 0..=0x7fu8 {
+            assert_eq!(
+                is_ascii_space(b),
+                is_python_whitespace(b as char),
+                "{:#x}",
+                b
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Splitting text into sentences.
+
+use crate::{Config, Stats};
+
+/// Characters that can end a sentence.
+const TERMINATORS: &[char] = &['.', '!', '?', '…'];
+
+/// Closing quotes and brackets that belong to the sentence they follow.
+pub(crate) const CLOSERS: &[char] = &['"', '\'', ')', ']', '”', '’', '»'];
+
+/// Opening quotes and brackets that may come before a sentence's first word.
+const OPENERS: &[char] = &['"', '\'', '(', '[', '“', '‘', '«'];
+
+/// Lowercase abbreviations, without their final full stop, that are
+/// usually followed by more of the same sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "al", "apr", "approx", "aug", "cf", "col", "dec", "dr", "feb", "fig", "gen", "jan", "jr",
+    "jul", "jun", "lt", "mr", "mrs", "ms", "mt", "nov", "oct", "prof", "rev", "sep", "sept", "sgt",
+    "sr", "st", "vol", "vs",
+];
+
+/// Returns an iterator over the sentences of `text`, as subslices with the
+/// surrounding whitespace trimmed.
+///
+/// A sentence ends at `.`, `!`, `?`, `…` or a run of them, together with
+/// any closing quotes or brackets, when whitespace and then anything but a
+/// lowercase letter follow. So "3.14" and "U.S.A." inside a word do not
+/// end one, nor does "so... then". A full stop after an abbreviation such
+/// as "Dr.", an initial such as "J." or dotted letters such as "U.S." or
+/// "e.g." does not end one either. A blank line always ends a sentence, so
+/// headings without punctuation stand alone.
+pub fn sentences(text: &str) -> Sentences<'_> {
+    Sentences { text, pos: 0 }
+}
+
+/// Counts each sentence of `text`, as [`stats`](crate::stats) would, in
+/// order. Sentences whose words are all stopwords are kept, with a ratio of
+/// [`Error::EmptyInput`](crate::Error::EmptyInput). Each record's
+/// `sentences` is 1, whatever its words would count on their own.
+pub fn sentence_stats(text: &str, config: &Config) -> Vec<Stats> {
+    sentences(text)
+        .map(|sentence| Stats {
+            sentences: 1,
+            ..crate::stats(sentence, config)
+        })
+        .collect()
+}
+
+/// Iterator over the sentences of a text, created by [`sentences`].
+#[derive(Clone, Debug)]
+pub struct Sentences<'a> {
+    text: &'a str,
+    /// Byte offset of the rest of the text.
+    pos: usize,
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = &self.text[self.pos..];
+        let start = self.pos + rest.len() - rest.trim_start().len();
+        if start == self.text.len() {
+            self.pos = start;
+            return None;
+        }
+        let end = sentence_end(self.text, start);
+        self.pos = end;
+        Some(self.text[start..end].trim_end())
+    }
+}
+
+/// Returns the byte offset just past the sentence that starts at `start`.
+fn sentence_end(text: &str, start: usize) -> usize {
+    for (i, c) in text[start..].char_indices() {
+        let i = start + i;
+        if c == '\n' && starts_with_blank_line(&text[i + 1..]) {
+            return i;
+        }
+        if !TERMINATORS.contains(&c) {
+            continue;
+        }
+        // Take the whole run of terminators and closing marks.
+        let run = &text[i..];
+        let after = i + run.len() - run.trim_start_matches(TERMINATORS).len();
+        let after = after + text[after..].len() - text[after..].trim_start_matches(CLOSERS).len();
+        let next = &text[after..];
+        if next.starts_with(|c: char| !c.is_whitespace()) {
+            // Inside a word, as in "3.14" or "U.S.A.".
+            continue;
+        }
+        let single_stop = text[i..after].trim_end_matches(CLOSERS) == ".";
+        if single_stop && is_abbreviation(last_word(&text[start..i])) {
+            continue;
+        }
+        let next_word = next.trim_start().trim_start_matches(OPENERS);
+        if !next_word.starts_with(char::is_lowercase) {
+            return after;
+        }
+    }
+    text.len()
+}
+
+/// Whether `text` starts with a line holding only whitespace.
+fn starts_with_blank_line(text: &str) -> bool {
+    let line = text.split('\n').next().unwrap_or("");
+    !text.is_empty() && line.trim().is_empty()
+}
+
+/// The last whitespace-separated word of `text`.
+fn last_word(text: &str) -> &str {
+    text.rsplit(char::is_whitespace).next().unwrap_or("")
+}
+
+/// Whether `word`, without the full stop after it, is an abbreviation, an
+/// initial or a run of dotted letters such as "U.S" or "Ph.D", which a
+/// full stop usually follows in mid-sentence.
+pub(crate) fn is_abbreviation(word: &str) -> bool {
+    let word = word.trim_start_matches(OPENERS);
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => false,
+        (Some(c), None) => c.is_alphabetic(),
+        _ if word.contains('.') => word
+            .split('.')
+            .all(|part| (1..=2).contains(&part.len()) && part.chars().all(char::is_alphabetic)),
+        _ => ABBREVIATIONS
+            .iter()
+            .any(|abbreviation| word.eq_ignore_ascii_case(abbreviation)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::corpus;
+
+    fn split(text: &str) -> Vec<&str> {
+        sentences(text).collect()
+    }
+
+    #[test]
+    fn splits_at_terminators() {
+        assert_eq!(
+            split("  It rained.  Did it? Yes!!\tIt did "),
+            ["It rained.", "Did it?", "Yes!!", "It did"]
+        );
+        assert!(split(" \n ").is_empty());
+        assert_eq!(split("No terminator"), ["No terminator"]);
+    }
+
+    #[test]
+    fn abbreviations_and_decimals_do_not_split() {
+        assert_eq!(
+            split("Dr. Smith paid $3.50 in the U.S. on Jan. 5. J. R. R. Tolkien, e.g. a writer, agreed."),
+            [
+                "Dr. Smith paid $3.50 in the U.S. on Jan. 5.",
+                "J. R. R. Tolkien, e.g. a writer, agreed."
+            ]
+        );
+        assert_eq!(
+            split("See fig. 3. It is clear."),
+            ["See fig. 3.", "It is clear."]
+        );
+    }
+
+    #[test]
+    fn ellipses_and_quotes() {
+        assert_eq!(
+            split(
+                "Wait... what? He said \"Stop.\" Then (quietly) \"Go!\" she said… “Fine.” (Done.)"
+            ),
+            [
+                "Wait... what?",
+                "He said \"Stop.\"",
+                "Then (quietly) \"Go!\" she said…",
+                "“Fine.”",
+                "(Done.)"
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_lines_end_sentences() {
+        assert_eq!(
+            split("Heading\n\nBody text\nwraps here. Next.\r\n \r\nLast"),
+            ["Heading", "Body text\nwraps here.", "Next.", "Last"]
+        );
+    }
+
+    #[test]
+    fn abbreviations() {
+        for word in ["Dr", "mrs", "\"St", "U.S", "e.g", "Ph.D", "J"] {
+            assert!(is_abbreviation(word), "{}", word);
+        }
+        for word in ["", "end", "3", "1.5", "etc", "U..S"] {
+            assert!(!is_abbreviation(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn sentences_cover_the_corpus() {
+        let text = corpus();
+        let stats = sentence_stats(&text, &Config::default());
+        assert_eq!(stats.len(), 1363);
+        // Every word lands in exactly one sentence.
+        let words: u64 = stats.iter().map(|s| s.total_tokens).sum();
+        assert_eq!(words, crate::stats(&text, &Config::default()).total_tokens);
+        assert!(stats.iter().all(|s| s.sentences == 1));
+    }
+
+    #[test]
+    fn each_record_is_one_sentence() {
+        let config = Config::default();
+        for text in ["So... then he left.", "Wait! What?", "A heading\n\nBody."] {
+            let counts: Vec<_> = sentence_stats(text, &config)
+                .iter()
+                .map(|s| s.sentences)
+                .collect();
+            assert_eq!(counts, vec![1; sentences(text).count()], "{}", text);
+        }
+    }
+}
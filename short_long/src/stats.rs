@@ -0,0 +1,315 @@
+//! Word counts behind the short/long ratio and the readability indices.
+
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use crate::readability::{self, Readability};
+use crate::{Config, Error};
+
+/// Number of counts in a [`Stats`].
+const FIELDS: usize = 9;
+
+/// The counts the short/long ratio and [`Readability`] scores are computed
+/// from.
+///
+/// Keeping the numerator and denominator separately lets results for many
+/// texts be summed before dividing, which a ratio alone does not allow.
+/// Stats form a monoid: [`Stats::default`] is the identity, and adding the
+/// stats of two texts gives the stats of their concatenation (split at
+/// whitespace), in any order or grouping. Partial results can be sent
+/// between processes with [`encode`](Stats::encode) and
+/// [`decode`](Stats::decode).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// Every word in the text, stopwords included.
+    pub total_tokens: u64,
+    /// Words left after removing stopwords: the ratio's denominator.
+    pub filtered_tokens: u64,
+    /// Filtered words longer than the threshold: the ratio's numerator.
+    pub long_tokens: u64,
+    /// Summed length of the filtered words, in the configured unit.
+    pub total_chars: u64,
+    /// Words containing a letter or digit, stopwords included.
+    pub words: u64,
+    /// Words that end a sentence. Each one counts on its own, so a
+    /// terminator that [`sentences`](crate::sentences) would skip, as in
+    /// "so... then", still counts, and a heading without one does not.
+    pub sentences: u64,
+    /// Estimated syllables in `words`.
+    pub syllables: u64,
+    /// Words of three or more syllables.
+    pub polysyllables: u64,
+    /// Letters and digits in `words`.
+    pub letters: u64,
+}
+
+impl Stats {
+    /// Returns `long_tokens / filtered_tokens`, or [`Error::EmptyInput`] if
+    /// no words were left after removing stopwords.
+    pub fn ratio(&self) -> Result<f64, Error> {
+        if self.filtered_tokens == 0 {
+            return Err(Error::EmptyInput);
+        }
+        Ok(self.long_tokens as f64 / self.filtered_tokens as f64)
+    }
+
+    /// Returns `polysyllables / words`, the fraction of words with three or
+    /// more syllables, or [`Error::EmptyInput`] if the text has no words.
+    pub fn polysyllabic_ratio(&self) -> Result<f64, Error> {
+        if self.words == 0 {
+            return Err(Error::EmptyInput);
+        }
+        Ok(self.polysyllables as f64 / self.words as f64)
+    }
+
+    /// Returns `words / sentences`, the average sentence length in words,
+    /// or [`Error::EmptyInput`] if the text has no words. A text with words
+    /// but no sentence terminator counts as one sentence.
+    pub fn words_per_sentence(&self) -> Result<f64, Error> {
+        if self.words == 0 {
+            return Err(Error::EmptyInput);
+        }
+        Ok(self.words as f64 / self.sentences.max(1) as f64)
+    }
+
+    /// Returns the readability indices, or [`Error::EmptyInput`] if the
+    /// text has no words.
+    pub fn readability(&self) -> Result<Readability, Error> {
+        Readability::new(self)
+    }
+
+    /// Counts one word of a text scored with `config`.
+    pub(crate) fn add_word(&mut self, word: &str, config: &Config) {
+        self.add_ratio_word(word, config);
+        let measured = readability::measure(word);
+        if measured.letters > 0 {
+            self.words += 1;
+            self.letters += measured.letters as u64;
+            self.syllables += measured.syllables as u64;
+            self.polysyllables += (measured.syllables >= 3) as u64;
+        }
+        self.sentences += measured.ends_sentence as u64;
+    }
+
+    /// Counts one word towards the ratio only, leaving the readability
+    /// counts alone. This is much cheaper than [`add_word`](Stats::add_word).
+    pub(crate) fn add_ratio_word(&mut self, word: &str, config: &Config) {
+        self.total_tokens += 1;
+        if config.is_stopword(word) {
+            return;
+        }
+        let len = config.unit.len(word) as u64;
+        self.filtered_tokens += 1;
+        self.total_chars += len;
+        if len > config.threshold as u64 {
+            self.long_tokens += 1;
+        }
+    }
+
+    /// Adds the counts of `other`, as if its text had been counted along
+    /// with this one.
+    ///
+    /// A count that would overflow, which only decoded counts can reach,
+    /// stays at `u64::MAX`; [`checked_add`](Stats::checked_add) reports it
+    /// instead.
+    pub fn merge(&mut self, other: &Stats) {
+        for (count, other) in self.fields_mut().into_iter().zip(other.fields()) {
+            *count = count.saturating_add(other);
+        }
+    }
+
+    /// Returns the sum of the counts of `self` and `other`, or `None` if any
+    /// of them overflows.
+    pub fn checked_add(&self, other: &Stats) -> Option<Stats> {
+        let mut sum = *self;
+        for (count, other) in sum.fields_mut().into_iter().zip(other.fields()) {
+            *count = count.checked_add(other)?;
+        }
+        Some(sum)
+    }
+
+    /// Longest possible output of [`encode`](Stats::encode).
+    pub const MAX_ENCODED_LEN: usize = 1 + FIELDS * 10;
+
+    /// Serializes the counts compactly: the number of counts in one byte,
+    /// then each count as an unsigned LEB128 varint.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Stats::MAX_ENCODED_LEN);
+        out.push(FIELDS as u8);
+        for mut count in self.fields() {
+            while count >= 0x80 {
+                out.push(count as u8 | 0x80);
+                count >>= 7;
+            }
+            out.push(count as u8);
+        }
+        out
+    }
+
+    /// Parses the output of [`encode`](Stats::encode). Counts missing from
+    /// an encoding with fewer fields are 0; an encoding with more fields
+    /// than this version knows, or malformed bytes, give `None`.
+    pub fn decode(bytes: &[u8]) -> Option<Stats> {
+        let (&n, mut rest) = bytes.split_first()?;
+        if n as usize > FIELDS {
+            return None;
+        }
+        let mut stats = Stats::default();
+        for count in stats.fields_mut().into_iter().take(n as usize) {
+            let mut shift = 0;
+            loop {
+                let (&b, tail) = rest.split_first()?;
+                rest = tail;
+                if shift == 63 && b > 1 {
+                    return None;
+                }
+                *count |= u64::from(b & 0x7f) << shift;
+                if b < 0x80 {
+                    break;
+                }
+                shift += 7;
+                if shift > 63 {
+                    return None;
+                }
+            }
+        }
+        rest.is_empty().then_some(stats)
+    }
+
+    /// The counts in encoding order. New counts are only ever appended.
+    fn fields(&self) -> [u64; FIELDS] {
+        [
+            self.total_tokens,
+            self.filtered_tokens,
+            self.long_tokens,
+            self.total_chars,
+            self.words,
+            self.sentences,
+            self.syllables,
+            self.polysyllables,
+            self.letters,
+        ]
+    }
+
+    fn fields_mut(&mut self) -> [&mut u64; FIELDS] {
+        [
+            &mut self.total_tokens,
+            &mut self.filtered_tokens,
+            &mut self.long_tokens,
+            &mut self.total_chars,
+            &mut self.words,
+            &mut self.sentences,
+            &mut self.syllables,
+            &mut self.polysyllables,
+            &mut self.letters,
+        ]
+    }
+}
+
+impl AddAssign for Stats {
+    fn add_assign(&mut self, other: Stats) {
+        self.merge(&other);
+    }
+}
+
+impl Add for Stats {
+    type Output = Stats;
+
+    fn add(mut self, other: Stats) -> Stats {
+        self.merge(&other);
+        self
+    }
+}
+
+impl Sum for Stats {
+    fn sum<I: Iterator<Item = Stats>>(iter: I) -> Stats {
+        iter.fold(Stats::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Stats> for Stats {
+    fn sum<I: Iterator<Item = &'a Stats>>(iter: I) -> Stats {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sum_of_parts_is_stats_of_whole() {
+        let config = Config::default();
+        let parts = ["The cat sat", " on a", "", " magnificent mat"];
+        let total: Stats = parts.iter().map(|p| crate::stats(p, &config)).sum();
+        assert_eq!(total, crate::stats(&parts.concat(), &config));
+        assert_eq!(total + Stats::default(), total);
+    }
+
+    #[test]
+    fn overflowing_counts_saturate_or_are_reported() {
+        let huge = Stats::decode(&[
+            4, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+        ])
+        .unwrap();
+        let one = Stats {
+            total_tokens: 1,
+            total_chars: 1,
+            ..Stats::default()
+        };
+        assert_eq!(huge.checked_add(&one), None);
+        assert_eq!(huge.checked_add(&Stats::default()), Some(huge));
+        let sum = huge + one;
+        assert_eq!((sum.total_tokens, sum.total_chars), (1, u64::MAX));
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        let stats = Stats {
+            total_tokens: 0,
+            filtered_tokens: 127,
+            long_tokens: 128,
+            total_chars: u64::MAX,
+            letters: 1,
+            ..Stats::default()
+        };
+        let bytes = stats.encode();
+        assert_eq!(bytes.len(), 1 + 1 + 1 + 2 + 10 + 5);
+        assert!(bytes.len() <= Stats::MAX_ENCODED_LEN);
+        assert_eq!(Stats::decode(&bytes), Some(stats));
+        assert_eq!(
+            Stats::decode(&Stats::default().encode()),
+            Some(Stats::default())
+        );
+    }
+
+    #[test]
+    fn malformed_encodings_are_rejected() {
+        assert_eq!(Stats::decode(&[]), None);
+        assert_eq!(Stats::decode(&[4, 1, 2, 3]), None);
+        assert_eq!(Stats::decode(&[4, 1, 2, 3, 4, 5]), None);
+        assert_eq!(Stats::decode(&[10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]), None);
+        assert_eq!(
+            Stats::decode(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]),
+            None
+        );
+        // Encodings from before the readability counts were added.
+        assert_eq!(
+            Stats::decode(&[4, 1, 2, 3, 4]),
+            Some(Stats {
+                total_tokens: 1,
+                filtered_tokens: 2,
+                long_tokens: 3,
+                total_chars: 4,
+                ..Stats::default()
+            })
+        );
+        assert_eq!(
+            Stats::decode(&[1, 7]),
+            Some(Stats {
+                total_tokens: 7,
+                ..Stats::default()
+            })
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Counting text that arrives in chunks.
+
+use std::str;
+
+use crate::{Config, Error, Stats};
+
+/// Accumulates [`Stats`] over text fed in arbitrary chunks, such as blocks
+/// read from a compressed dump, without ever holding the whole text.
+///
+/// A word or a multi-byte UTF-8 character split across two chunks is
+/// counted once, as if the chunks had been joined. The result of
+/// [`finish`](Accumulator::finish) is the same as [`stats`](crate::stats)
+/// on the concatenated text.
+#[derive(Clone, Debug)]
+pub struct Accumulator {
+    config: Config,
+    stats: Stats,
+    /// Bytes after the last separator seen, which may still continue in the
+    /// next chunk.
+    pending: Vec<u8>,
+    /// Length of the start of `pending` known to be complete UTF-8
+    /// characters.
+    checked: usize,
+    /// Bytes fed so far, not counting `pending`.
+    consumed: usize,
+}
+
+impl Accumulator {
+    pub fn new(config: Config) -> Accumulator {
+        Accumulator {
+            config,
+            stats: Stats::default(),
+            pending: Vec::new(),
+            checked: 0,
+            consumed: 0,
+        }
+    }
+
+    /// The config the text is counted with.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Counts the next chunk of text. Words at the end of the chunk are held
+    /// back until the next separator or [`finish`](Accumulator::finish).
+    ///
+    /// If the text is not UTF-8, the error gives the offset of the first
+    /// invalid sequence from the start of the whole text, and the
+    /// accumulator should be discarded.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        // Only the new bytes, and a character the last chunk cut short, need
+        // checking and searching, so feeding stays linear in the text even
+        // when chunks hold no separator.
+        let start = self.checked;
+        self.pending.extend_from_slice(chunk);
+        let end = match str::from_utf8(&self.pending[start..]) {
+            Ok(_) => self.pending.len(),
+            // The chunk ends in the middle of a character.
+            Err(e) if e.error_len().is_none() => start + e.valid_up_to(),
+            Err(e) => {
+                return Err(Error::InvalidUtf8 {
+                    valid_up_to: self.consumed + start + e.valid_up_to(),
+                })
+            }
+        };
+        let tokenizer = self.config.tokenizer;
+        match str::from_utf8(&self.pending[start..end])
+            .unwrap()
+            .char_indices()
+            .rfind(|&(_, c)| tokenizer.is_separator(c))
+        {
+            Some((i, c)) => {
+                let i = start + i;
+                let text = str::from_utf8(&self.pending[..i]).unwrap();
+                self.stats.merge(&crate::stats(text, &self.config));
+                let rest = i + c.len_utf8();
+                self.pending.drain(..rest);
+                self.consumed += rest;
+                self.checked = end - rest;
+            }
+            None => self.checked = end,
+        }
+        Ok(())
+    }
+
+    /// Counts the held back words and returns the totals for everything
+    /// fed.
+    pub fn finish(self) -> Result<Stats, Error> {
+        let text = str::from_utf8(&self.pending).map_err(|e| Error::InvalidUtf8 {
+            valid_up_to: self.consumed + e.valid_up_to(),
+        })?;
+        let mut stats = self.stats;
+        stats.merge(&crate::stats(text, &self.config));
+        Ok(stats)
+    }
+}
+
+/// Counts text given as a sequence of chunks, as [`Accumulator`] does.
+pub fn stats_from_chunks<I>(chunks: I, config: &Config) -> Result<Stats, Error>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let mut accumulator = Accumulator::new(config.clone());
+    for chunk in chunks {
+        accumulator.feed(chunk.as_ref())?;
+    }
+    accumulator.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    const TEXT: &str = "Les  élèves\u{a0}étudient\nthe café's crème brûlée a\u{3000}Ω ";
+
+    #[test]
+    fn every_split_matches_whole_text() {
+        for tokenizer in [Tokenizer::Python, Tokenizer::Legacy] {
+            let config = Config {
+                tokenizer,
+                ..Config::with_threshold(4)
+            };
+            let whole = crate::stats(TEXT, &config);
+            let bytes = TEXT.as_bytes();
+            for i in 0..=bytes.len() {
+                for j in i..=bytes.len() {
+                    let chunks = [&bytes[..i], &bytes[i..j], &bytes[j..]];
+                    assert_eq!(
+                        stats_from_chunks(chunks, &config),
+                        Ok(whole),
+                        "{:?} split at {} and {}",
+                        tokenizer,
+                        i,
+                        j
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn one_byte_chunks_match_whole_text() {
+        let config = Config::default();
+        let chunks = TEXT.as_bytes().chunks(1);
+        assert_eq!(
+            stats_from_chunks(chunks, &config),
+            Ok(crate::stats(TEXT, &config))
+        );
+    }
+
+    #[test]
+    fn long_word_in_tiny_chunks_is_fed_in_linear_time() {
+        // Rescanning everything held back on every feed would take minutes.
+        let text = "é".repeat(500_000) + " end";
+        let config = Config::default();
+        let chunks = text.as_bytes().chunks(1);
+        assert_eq!(
+            stats_from_chunks(chunks, &config),
+            Ok(crate::stats(&text, &config))
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_offset_is_absolute() {
+        let config = Config::default();
+        let chunks: [&[u8]; 3] = [b"one two", b" thr\xc3", b"\x28"];
+        assert_eq!(
+            stats_from_chunks(chunks, &config),
+            Err(Error::InvalidUtf8 { valid_up_to: 11 })
+        );
+        let chunks: [&[u8]; 2] = [b"one two", b" thr\xc3"];
+        assert_eq!(
+            stats_from_chunks(chunks, &config),
+            Err(Error::InvalidUtf8 { valid_up_to: 11 })
+        );
+    }
+}
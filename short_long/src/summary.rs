@@ -0,0 +1,81 @@
+//! Descriptive statistics over many ratios, such as one per line.
+
+/// The distribution of a set of ratios: mean, extremes and percentiles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    sorted: Vec<f64>,
+    mean: f64,
+}
+
+impl Summary {
+    /// Summarizes `values`, skipping NaNs. Returns `None` if no values are
+    /// left.
+    pub fn of(values: impl IntoIterator<Item = f64>) -> Option<Summary> {
+        let mut sorted: Vec<f64> = values.into_iter().filter(|v| !v.is_nan()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let mean = sorted.iter().sum::<f64>() / sorted.len() as f64;
+        Some(Summary { sorted, mean })
+    }
+
+    /// Number of values summarized.
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Arithmetic mean.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Smallest value.
+    pub fn min(&self) -> f64 {
+        self.sorted[0]
+    }
+
+    /// Largest value.
+    pub fn max(&self) -> f64 {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    /// The 50th percentile.
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// The `p`th percentile, for `p` from 0 to 100 (clamped), interpolating
+    /// linearly between the closest values like NumPy's default.
+    pub fn percentile(&self, p: f64) -> f64 {
+        let rank = p.clamp(0.0, 100.0) / 100.0 * (self.sorted.len() - 1) as f64;
+        let below = rank.floor() as usize;
+        let above = rank.ceil() as usize;
+        let (lo, hi) = (self.sorted[below], self.sorted[above]);
+        lo + (hi - lo) * (rank - below as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_interpolate() {
+        let summary = Summary::of([0.4, f64::NAN, 0.1, 0.3, 0.2]).unwrap();
+        assert_eq!(summary.count(), 4);
+        assert_eq!(summary.min(), 0.1);
+        assert_eq!(summary.max(), 0.4);
+        assert!((summary.mean() - 0.25).abs() < 1e-12);
+        assert!((summary.median() - 0.25).abs() < 1e-12);
+        assert!((summary.percentile(90.0) - 0.37).abs() < 1e-12);
+        assert_eq!(summary.percentile(150.0), 0.4);
+    }
+
+    #[test]
+    fn nothing_to_summarize() {
+        assert_eq!(Summary::of([]), None);
+        assert_eq!(Summary::of([f64::NAN]), None);
+        assert_eq!(Summary::of([0.5]).unwrap().percentile(10.0), 0.5);
+    }
+}
@@ -0,0 +1,222 @@
+//! Splitting text into words and measuring them.
+
+use std::fmt;
+use std::str::{FromStr, Split};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::scan::{Scanner, Words};
+
+/// How text is split into words.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Tokenizer {
+    /// Splits on runs of whitespace and drops empty words, exactly like
+    /// Python's `str.split()` with no arguments.
+    #[default]
+    Python = 0,
+    /// Splits on every single whitespace character, so doubled spaces and
+    /// line breaks produce empty words. This is how the ratio was originally
+    /// computed.
+    Legacy = 1,
+}
+
+impl Tokenizer {
+    /// Returns an iterator over the words of `text`.
+    pub fn tokens(self, text: &str) -> Tokens<'_> {
+        match self {
+            Tokenizer::Python => Tokens(Inner::Python(Words::new(text, Scanner::detect()))),
+            Tokenizer::Legacy => Tokens(Inner::Legacy(text.split(char::is_whitespace))),
+        }
+    }
+
+    /// Whether `c` separates words.
+    pub fn is_separator(self, c: char) -> bool {
+        match self {
+            Tokenizer::Python => is_python_whitespace(c),
+            Tokenizer::Legacy => c.is_whitespace(),
+        }
+    }
+}
+
+impl FromStr for Tokenizer {
+    type Err = UnknownName;
+
+    /// Parses the lowercase variant name, `python` or `legacy`.
+    fn from_str(name: &str) -> Result<Tokenizer, UnknownName> {
+        match name {
+            "python" => Ok(Tokenizer::Python),
+            "legacy" => Ok(Tokenizer::Legacy),
+            _ => Err(UnknownName::new("tokenizer", name)),
+        }
+    }
+}
+
+impl TryFrom<u32> for Tokenizer {
+    type Error = UnknownName;
+
+    /// Converts a discriminant, as passed in from C.
+    fn try_from(value: u32) -> Result<Tokenizer, UnknownName> {
+        match value {
+            0 => Ok(Tokenizer::Python),
+            1 => Ok(Tokenizer::Legacy),
+            _ => Err(UnknownName::new("tokenizer", &value.to_string())),
+        }
+    }
+}
+
+/// Iterator over the words of a string, created by [`Tokenizer::tokens`].
+#[derive(Clone, Debug)]
+pub struct Tokens<'a>(Inner<'a>);
+
+#[derive(Clone, Debug)]
+enum Inner<'a> {
+    Python(Words<'a>),
+    Legacy(Split<'a, fn(char) -> bool>),
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        match &mut self.0 {
+            Inner::Python(words) => words.next(),
+            Inner::Legacy(split) => split.next(),
+        }
+    }
+}
+
+/// What a word's length is measured in.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    /// UTF-8 bytes, as the ratio was originally computed.
+    Bytes = 0,
+    /// Unicode scalar values (code points), like Python's `len()` on `str`.
+    #[default]
+    Chars = 1,
+    /// Extended grapheme clusters, i.e. user-perceived characters.
+    Graphemes = 2,
+}
+
+impl LengthUnit {
+    /// Returns the length of `word` in this unit.
+    pub fn len(self, word: &str) -> usize {
+        match self {
+            LengthUnit::Bytes => word.len(),
+            LengthUnit::Chars => word.chars().count(),
+            LengthUnit::Graphemes => word.graphemes(true).count(),
+        }
+    }
+}
+
+impl FromStr for LengthUnit {
+    type Err = UnknownName;
+
+    /// Parses the lowercase variant name: `bytes`, `chars` or `graphemes`.
+    fn from_str(name: &str) -> Result<LengthUnit, UnknownName> {
+        match name {
+            "bytes" => Ok(LengthUnit::Bytes),
+            "chars" => Ok(LengthUnit::Chars),
+            "graphemes" => Ok(LengthUnit::Graphemes),
+            _ => Err(UnknownName::new("unit", name)),
+        }
+    }
+}
+
+impl TryFrom<u32> for LengthUnit {
+    type Error = UnknownName;
+
+    /// Converts a discriminant, as passed in from C.
+    fn try_from(value: u32) -> Result<LengthUnit, UnknownName> {
+        match value {
+            0 => Ok(LengthUnit::Bytes),
+            1 => Ok(LengthUnit::Chars),
+            2 => Ok(LengthUnit::Graphemes),
+            _ => Err(UnknownName::new("unit", &value.to_string())),
+        }
+    }
+}
+
+/// Error parsing a [`Tokenizer`] or [`LengthUnit`] from an unrecognized
+/// name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownName {
+    kind: &'static str,
+    name: String,
+}
+
+impl UnknownName {
+    fn new(kind: &'static str, name: &str) -> UnknownName {
+        UnknownName {
+            kind,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {} {:?}", self.kind, self.name)
+    }
+}
+
+impl std::error::Error for UnknownName {}
+
+/// Whether Python's `str.isspace()` is true for `c`. This is Unicode
+/// `White_Space` plus the ASCII file, group, record and unit separators.
+pub fn is_python_whitespace(c: char) -> bool {
+    c.is_whitespace() || ('\x1c'..='\x1f').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::corpus;
+
+    #[test]
+    fn python_collapses_runs_of_whitespace() {
+        let text = "  one  two\n\nthree\x1ffour\u{3000}";
+        let words: Vec<_> = Tokenizer::Python.tokens(text).collect();
+        assert_eq!(words, ["one", "two", "three", "four"]);
+        assert_eq!(Tokenizer::Python.tokens(" \n\t").count(), 0);
+    }
+
+    #[test]
+    fn names_parse() {
+        assert_eq!("legacy".parse(), Ok(Tokenizer::Legacy));
+        assert_eq!("graphemes".parse(), Ok(LengthUnit::Graphemes));
+        let err = "Chars".parse::<LengthUnit>().unwrap_err();
+        assert_eq!(err.to_string(), r#"unknown unit "Chars""#);
+    }
+
+    #[test]
+    fn legacy_keeps_empty_words() {
+        let words: Vec<_> = Tokenizer::Legacy.tokens(" one  two").collect();
+        assert_eq!(words, ["", "one", "", "two"]);
+    }
+
+    #[test]
+    fn length_units_differ_on_non_ascii() {
+        // "e" followed by a combining acute accent.
+        let word = "cafe\u{301}s";
+        assert_eq!(LengthUnit::Bytes.len(word), 7);
+        assert_eq!(LengthUnit::Chars.len(word), 6);
+        assert_eq!(LengthUnit::Graphemes.len(word), 5);
+    }
+
+    #[test]
+    fn python_matches_reference_on_corpus() {
+        let text = corpus();
+        // From `inPython.py`: `string.split()` minus "the" and "a" leaves
+        // 170892 words, of which 23220 have `len()` above 8, and 23496 are
+        // longer than 8 UTF-8 bytes.
+        let config = crate::Config::default();
+        let words = Tokenizer::Python
+            .tokens(&text)
+            .filter(|w| !config.is_stopword(w));
+        assert_eq!(words.clone().count(), 170892);
+        assert_eq!(words.filter(|w| w.len() > 8).count(), 23496);
+        assert_eq!(crate::ratio(&text), 23220.0 / 170892.0);
+    }
+}
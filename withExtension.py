@@ -0,0 +1,18 @@
+import timeit
+import short_long
+
+#LOAD STRING
+example = ""
+
+with open("formalSentences.txt",encoding="utf8") as f:
+    for line in f:
+        example = example + line
+
+#PRINT RESULT
+print("With the Rust extension module: ")
+print(str(int(short_long.short_long(example) * 100)) + "%")
+
+
+
+#TIMEIT
+print("Elapsed time: {:.5f}s".format(sum(timeit.repeat("short_long.short_long(example)", globals=globals(),repeat=20,number=1))/20))
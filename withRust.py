@@ -1,11 +1,16 @@
+import sys
 import timeit
 from cffi import FFI
 ffi = FFI()
-lib = ffi.dlopen("short_long/target/release/short_long.dll")
-ffi.cdef("""
-    double short_long(const char *, uint64_t);
-"""
-)
+if sys.platform == "win32":
+    lib = ffi.dlopen("short_long/target/release/short_long.dll")
+elif sys.platform == "darwin":
+    lib = ffi.dlopen("short_long/target/release/libshort_long.dylib")
+else:
+    lib = ffi.dlopen("short_long/target/release/libshort_long.so")
+#CFFI can't read preprocessor lines, but everything else in the generated header is plain C
+with open("short_long/short_long.h") as f:
+    ffi.cdef("".join(line for line in f if not line.startswith("#")))
 
 #LOAD STRING
 example = ""
@@ -18,7 +23,7 @@ with open("formalSentences.txt",encoding="utf8") as f:
 print("With Rust: ")
 string = example.encode()
 cstr = ffi.new("char[]", string)
-print(str(int(lib.short_long(cstr,len(cstr)) * 100)) + "%")
+print(str(int(lib.short_long(cstr,len(string)) * 100)) + "%")
 
 
 
@@ -26,7 +31,7 @@ print(str(int(lib.short_long(cstr,len(cstr)) * 100)) + "%")
 the_code = """
 string = example.encode()
 cstr = ffi.new("char[]", string)
-lib.short_long(cstr,len(cstr))
+lib.short_long(cstr,len(string))
 """
 
 print("Elapsed time: {:.5f}s".format(sum(timeit.repeat(the_code, globals=globals(),repeat=20,number=1))/20))
\ No newline at end of file